@@ -0,0 +1,66 @@
+use serde_json::{json, Value as JsonValue};
+
+use super::{is_nullable, merged_keys, value_kind, IntegerWidth, ValueKind};
+use crate::{Schema, SchemaValueType};
+
+impl Schema {
+    /// Generates an Apache Arrow schema, in the JSON format of Arrow's integration tests.
+    ///
+    /// Objects become `Struct`s and arrays `List`s of a field called `item`. Strings are `Utf8`,
+    /// integers `Int64`, or `UInt64` above its range, and floats `Float64`. Fields are nullable
+    /// when `NULL` was found or the key is missing in some objects. The object shapes of a key
+    /// are merged into a single `Struct`, as Parquet has no unions. Other keys with several
+    /// types, and objects without keys, as Parquet has no empty groups, are `Utf8` fields of
+    /// the canonical `arrow.json` extension type, holding the values as JSON text.
+    pub fn to_arrow(&self) -> JsonValue {
+        json!({ "fields": fields(&[self]) })
+    }
+}
+
+// The fields of the shapes, a key missing in some of them is nullable
+fn fields(shapes: &[&Schema]) -> Vec<JsonValue> {
+    merged_keys(shapes)
+        .into_iter()
+        .map(|(key, (v_types, is_required))| field(key, &v_types, is_required))
+        .collect()
+}
+
+fn field(name: &str, v_types: &[SchemaValueType], is_required: bool) -> JsonValue {
+    let kind = value_kind(v_types);
+    let nullable = matches!(kind, ValueKind::Null) || is_nullable(v_types) || !is_required;
+    let mut metadata = Vec::new();
+
+    let (arrow_type, children) = match kind {
+        ValueKind::Null => (json!({ "name": "null" }), Vec::new()),
+        ValueKind::Bool => (json!({ "name": "bool" }), Vec::new()),
+        ValueKind::Integer(IntegerWidth::I64) => (json!({ "name": "int", "isSigned": true, "bitWidth": 64 }), Vec::new()),
+        ValueKind::Integer(IntegerWidth::U64) => (json!({ "name": "int", "isSigned": false, "bitWidth": 64 }), Vec::new()),
+        ValueKind::Integer(IntegerWidth::Wider) => (json!({ "name": "decimal", "precision": 20, "scale": 0, "bitWidth": 128 }), Vec::new()),
+        ValueKind::Float => (json!({ "name": "floatingpoint", "precision": "DOUBLE" }), Vec::new()),
+        ValueKind::String => (json!({ "name": "utf8" }), Vec::new()),
+        ValueKind::Array(items) => (json!({ "name": "list" }), vec![field("item", &items, true)]),
+        ValueKind::Object(shapes) => match fields(&shapes) {
+            children if children.is_empty() => json_text(&mut metadata),
+            children => (json!({ "name": "struct" }), children),
+        },
+        ValueKind::Mixed => json_text(&mut metadata),
+    };
+
+    let mut field = json!({
+        "name": name,
+        "nullable": nullable,
+        "type": arrow_type,
+        "children": children,
+    });
+    if !metadata.is_empty() {
+        field["metadata"] = metadata.into();
+    }
+    field
+}
+
+// A `Utf8` of the `arrow.json` extension type, for values that have no other Arrow type
+fn json_text(metadata: &mut Vec<JsonValue>) -> (JsonValue, Vec<JsonValue>) {
+    metadata.push(json!({ "key": "ARROW:extension:name", "value": "arrow.json" }));
+    metadata.push(json!({ "key": "ARROW:extension:metadata", "value": "" }));
+    (json!({ "name": "utf8" }), Vec::new())
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Value as JsonValue};
+
+use super::{integer_width, is_nullable, non_null_types, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaError, SchemaRoot, SchemaValueType};
+
+impl Schema {
+    /// Generates an Apache Avro schema (`.avsc`) with a `record` for the schema and every
+    /// schema nested in it.
+    ///
+    /// Keys that can be `NULL` become `["null", T]` unions, keys that are not present in
+    /// every object also default to `null`. Keys that aren't valid Avro names are changed
+    /// into one, e.g. `country-code` becomes `country_code`.
+    ///
+    /// Returns `SchemaError::UnsupportedType` for types Avro has no equivalent for, such as
+    /// arrays that were always empty, as Avro needs to know the type of their items.
+    pub fn to_avro(&self) -> Result<JsonValue, SchemaError> {
+        let names = TypeNames::new(self, &[]);
+        record(&names, self, &self.name)
+    }
+}
+
+impl SchemaRoot {
+    /// Generates an Apache Avro schema for any root, see `Schema::to_avro`.
+    /// Other roots than objects use the type of their values, e.g. `{ "type": "array", "items": "long" }`.
+    pub fn to_avro(&self) -> Result<JsonValue, SchemaError> {
+        match self {
+            SchemaRoot::Object(schema) => schema.to_avro(),
+            SchemaRoot::Value(v_types) => union(&TypeNames::for_root(self, &[]), v_types, "root"),
+        }
+    }
+}
+
+fn record(names: &TypeNames, schema: &Schema, path: &str) -> Result<JsonValue, SchemaError> {
+    let mut field_names = HashSet::new();
+    let mut fields = Vec::new();
+
+    for (key, v_types) in &schema.map {
+        let path = format!("{}.{}", path, key);
+        let mut field = serde_json::Map::new();
+        field.insert("name".into(), unique_field_name(&mut field_names, avro_name(key)).into());
+
+        let avro_type = union(names, v_types, &path)?;
+        match schema.is_required(key) {
+            true => {
+                field.insert("type".into(), avro_type);
+            }
+            // A default is needed to read records without the key, and it has to match the first type of the union
+            false => {
+                field.insert("type".into(), with_null(avro_type));
+                field.insert("default".into(), JsonValue::Null);
+            }
+        }
+        fields.push(JsonValue::Object(field));
+    }
+
+    Ok(json!({
+        "type": "record",
+        "name": names.name_of(schema),
+        "fields": fields,
+    }))
+}
+
+// A single type is used as-is, several types become a union with `null` first
+fn union(names: &TypeNames, v_types: &[SchemaValueType], path: &str) -> Result<JsonValue, SchemaError> {
+    let mut types = Vec::new();
+    if is_nullable(v_types) {
+        types.push(JsonValue::from("null"));
+    }
+    for v_type in non_null_types(v_types) {
+        types.push(avro_type(names, v_type, path)?);
+    }
+
+    match types.len() {
+        1 => Ok(types.remove(0)),
+        _ => Ok(JsonValue::Array(types)),
+    }
+}
+
+fn with_null(avro_type: JsonValue) -> JsonValue {
+    match avro_type {
+        JsonValue::Array(types) if types.first() == Some(&JsonValue::from("null")) => JsonValue::Array(types),
+        JsonValue::Array(mut types) => {
+            types.insert(0, "null".into());
+            JsonValue::Array(types)
+        }
+        JsonValue::String(name) if name == "null" => name.into(),
+        avro_type => JsonValue::Array(vec!["null".into(), avro_type]),
+    }
+}
+
+fn avro_type(names: &TypeNames, v_type: &SchemaValueType, path: &str) -> Result<JsonValue, SchemaError> {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => Ok("boolean".into()),
+        SchemaValueType::Primitive(_) => Err(unsupported(path, "the type is unknown")),
+        SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+            IntegerWidth::I64 => Ok("long".into()),
+            IntegerWidth::U64 | IntegerWidth::Wider => Err(unsupported(path, "integers above the range of `long` were found")),
+        },
+        SchemaValueType::Float(..) => Ok("double".into()),
+        SchemaValueType::String(..) => Ok("string".into()),
+        SchemaValueType::Array(v_types) if v_types.is_empty() => Err(unsupported(path, "arrays need an item type, but only empty arrays were found")),
+        SchemaValueType::Array(v_types) => Ok(json!({
+            "type": "array",
+            "items": union(names, v_types, &format!("{}[]", path))?,
+        })),
+        SchemaValueType::Object(schema) => record(names, schema, path),
+    }
+}
+
+fn unsupported(path: &str, reason: &'static str) -> SchemaError {
+    SchemaError::UnsupportedType {
+        format: "Avro",
+        path: path.into(),
+        reason,
+    }
+}
+
+// Avro names only contain letters, digits and underscores, and don't start with a digit
+pub(super) fn avro_name(key: &str) -> String {
+    let name = key
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect::<String>();
+
+    match name.chars().next() {
+        None => "_".into(),
+        Some(first) if first.is_ascii_digit() => format!("_{}", name),
+        Some(_) => name,
+    }
+}
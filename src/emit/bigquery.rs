@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Value as JsonValue};
+
+use super::avro::avro_name;
+use super::{is_nullable, merged_keys, value_kind, IntegerWidth, ValueKind};
+use crate::{Schema, SchemaValueType};
+
+impl Schema {
+    /// Generates a BigQuery table schema, as used by `bq load --schema`.
+    ///
+    /// Keys that were present in every object and never `NULL` are `REQUIRED`, the others
+    /// `NULLABLE`, and arrays are `REPEATED`. Objects become `RECORD`s, with the shapes of a key
+    /// merged into one. BigQuery has no unions, so keys with several types, arrays of arrays and
+    /// objects without keys are `JSON`. Keys are changed into valid column names.
+    pub fn to_bigquery(&self) -> JsonValue {
+        JsonValue::Array(fields(&[self]))
+    }
+}
+
+fn fields(shapes: &[&Schema]) -> Vec<JsonValue> {
+    let mut field_names = HashSet::new();
+    merged_keys(shapes)
+        .into_iter()
+        .map(|(key, (v_types, is_required))| {
+            let name = column_name(&mut field_names, avro_name(key));
+            field(&name, &v_types, is_required)
+        })
+        .collect()
+}
+
+// Column names are case-insensitive, so `c` and `C` clash and the second gets a numbered suffix
+fn column_name(used: &mut HashSet<String>, base: String) -> String {
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while !used.insert(name.to_lowercase()) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+fn field(name: &str, v_types: &[SchemaValueType], is_required: bool) -> JsonValue {
+    let (bigquery_type, fields, mode) = match value_kind(v_types) {
+        ValueKind::Array(items) => {
+            let (bigquery_type, fields) = bigquery_type(value_kind(&items));
+            (bigquery_type, fields, "REPEATED")
+        }
+        kind => {
+            let mode = match is_required && !is_nullable(v_types) {
+                true => "REQUIRED",
+                false => "NULLABLE",
+            };
+            let (bigquery_type, fields) = bigquery_type(kind);
+            (bigquery_type, fields, mode)
+        }
+    };
+
+    let mut field = json!({
+        "name": name,
+        "type": bigquery_type,
+        "mode": mode,
+    });
+    if let Some(fields) = fields {
+        field["fields"] = fields.into();
+    }
+    field
+}
+
+// The type of a field, and the fields of a `RECORD`
+fn bigquery_type(kind: ValueKind) -> (&'static str, Option<Vec<JsonValue>>) {
+    match kind {
+        ValueKind::Bool => ("BOOLEAN", None),
+        ValueKind::Integer(IntegerWidth::I64) => ("INTEGER", None),
+        ValueKind::Integer(_) => ("NUMERIC", None),
+        ValueKind::Float => ("FLOAT", None),
+        ValueKind::String => ("STRING", None),
+        ValueKind::Object(shapes) => match fields(&shapes) {
+            fields if fields.is_empty() => ("JSON", None),
+            fields => ("RECORD", Some(fields)),
+        },
+        ValueKind::Null | ValueKind::Array(_) | ValueKind::Mixed => ("JSON", None),
+    }
+}
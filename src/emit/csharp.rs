@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use super::{integer_width, is_nullable, non_null_types, pascal_case, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaValueType};
+
+// Types from the generated usings that generated classes must not shadow
+const RESERVED: &[&str] = &["Boolean", "Double", "Int64", "JsonElement", "JsonPropertyName", "List", "Object", "String"];
+
+impl Schema {
+    /// Generates C# classes for System.Text.Json for the schema and all schemas nested in it.
+    ///
+    /// Properties are PascalCase, with `[JsonPropertyName]` for the keys they don't match.
+    /// Keys that are present in every object are `required`, keys that can be `NULL` or are
+    /// not always present are nullable. Arrays become `List<T>` and keys with several types
+    /// fall back to `JsonElement`.
+    pub fn to_csharp(&self) -> String {
+        let names = TypeNames::new(self, RESERVED);
+        let mut emitter = CSharpEmitter {
+            names: &names,
+            uses_json_element: false,
+            uses_list: false,
+        };
+
+        let classes = names
+            .schemas()
+            .map(|(name, schema)| emitter.class(name, schema))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut usings = Vec::new();
+        if emitter.uses_list {
+            usings.push("using System.Collections.Generic;\n");
+        }
+        if emitter.uses_json_element {
+            usings.push("using System.Text.Json;\n");
+        }
+        usings.push("using System.Text.Json.Serialization;\n");
+
+        format!("#nullable enable\n\n{}\n{}", usings.concat(), classes)
+    }
+}
+
+struct CSharpEmitter<'a> {
+    names: &'a TypeNames<'a>,
+    uses_json_element: bool,
+    uses_list: bool,
+}
+
+impl CSharpEmitter<'_> {
+    fn class(&mut self, name: &str, schema: &Schema) -> String {
+        // Members can't have the name of the class they are in
+        let mut property_names = HashSet::new();
+        property_names.insert(name.to_string());
+
+        let mut properties = Vec::new();
+        for (key, v_types) in &schema.map {
+            let is_required = schema.is_required(key);
+            let mut cs_type = self.cs_type(v_types);
+            if !is_required && !cs_type.ends_with('?') {
+                cs_type.push('?');
+            }
+
+            let mut property = String::new();
+            let property_name = unique_field_name(&mut property_names, pascal_case(key));
+            if property_name != *key {
+                property.push_str(&format!("    [JsonPropertyName({})]\n", serde_json::Value::String(key.clone())));
+            }
+            let modifier = if is_required { "required " } else { "" };
+            property.push_str(&format!("    public {}{} {} {{ get; set; }}\n", modifier, cs_type, property_name));
+            properties.push(property);
+        }
+
+        format!("public class {}\n{{\n{}}}\n", name, properties.join("\n"))
+    }
+
+    fn cs_type(&mut self, v_types: &[SchemaValueType]) -> String {
+        let cs_type = match non_null_types(v_types)[..] {
+            [v_type] => self.single_type(v_type),
+            _ => self.json_element(),
+        };
+
+        match is_nullable(v_types) {
+            true => format!("{}?", cs_type),
+            false => cs_type,
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+            SchemaValueType::Primitive(_) => self.json_element(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "long".into(),
+                IntegerWidth::U64 => "ulong".into(),
+                IntegerWidth::Wider => "decimal".into(),
+            },
+            SchemaValueType::Float(..) => "double".into(),
+            SchemaValueType::String(..) => "string".into(),
+            SchemaValueType::Array(v_types) => {
+                self.uses_list = true;
+                format!("List<{}>", self.cs_type(v_types))
+            }
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn json_element(&mut self) -> String {
+        self.uses_json_element = true;
+        "JsonElement".into()
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use super::{integer_width, is_nullable, non_null_types, pascal_case, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+impl Schema {
+    /// Generates Go structs with `json` tags for the schema and all schemas nested in it,
+    /// in a file of `package schema`.
+    ///
+    /// Keys that can be `NULL` or are not present in every object become pointers, the latter
+    /// are also tagged `omitempty`. Arrays become slices and keys with several types become
+    /// `json.RawMessage`, to be decoded once the actual type is known. Keys that a `json` tag
+    /// can't name, like the empty key, are left out with a comment.
+    pub fn to_go(&self) -> String {
+        go(&TypeNames::new(self, &[]))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Go types for any root, see `Schema::to_go`.
+    /// Other roots than objects get a type of their own, e.g. `type Root []json.RawMessage`.
+    pub fn to_go(&self) -> String {
+        go(&TypeNames::for_root(self, &[]))
+    }
+}
+
+fn go(names: &TypeNames) -> String {
+    let mut emitter = GoEmitter { names, uses_json: false };
+
+    let alias = names
+        .alias()
+        .map(|(name, v_types)| format!("type {} {}\n", name, emitter.go_type(v_types)));
+    let structs = alias
+        .into_iter()
+        .chain(
+            names
+                .schemas()
+                .map(|(name, schema)| emitter.structure(name, schema)),
+        )
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let mut go = String::from("package schema\n\n");
+    if emitter.uses_json {
+        go.push_str("import \"encoding/json\"\n\n");
+    }
+    go.push_str(&structs);
+    go
+}
+
+struct GoEmitter<'a> {
+    names: &'a TypeNames<'a>,
+    uses_json: bool,
+}
+
+impl GoEmitter<'_> {
+    fn structure(&mut self, name: &str, schema: &Schema) -> String {
+        let mut field_names = HashSet::new();
+        let mut fields = Vec::new();
+        let mut skipped = Vec::new();
+
+        for (key, v_types) in &schema.map {
+            if !is_valid_tag(key) {
+                skipped.push(key);
+                continue;
+            }
+
+            let required = schema.is_required(key);
+            let mut go_type = self.go_type(v_types);
+            if !required && !is_nillable(&go_type) {
+                go_type = format!("*{}", go_type);
+            }
+
+            let tag = match required {
+                true => format!("json:{}", quote(key)),
+                false => format!("json:{}", quote(&format!("{},omitempty", key))),
+            };
+            fields.push((unique_field_name(&mut field_names, field_name(key)), go_type, format!("`{}`", tag)));
+        }
+
+        // Aligned in columns, the way gofmt does
+        let name_width = fields
+            .iter()
+            .map(|(name, ..)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let type_width = fields
+            .iter()
+            .map(|(_, go_type, _)| go_type.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut go = format!("type {} struct {{\n", name);
+        for key in skipped {
+            go.push_str(&format!("\t// The key {} can't be named by a json tag\n", quote(key)));
+        }
+        for (field_name, go_type, tag) in fields {
+            go.push_str(&format!(
+                "\t{:name_width$} {:type_width$} {}\n",
+                field_name,
+                go_type,
+                tag,
+                name_width = name_width,
+                type_width = type_width
+            ));
+        }
+        go.push_str("}\n");
+        go
+    }
+
+    // Values that can be null become pointers, unless their type can be nil already
+    fn go_type(&mut self, v_types: &[SchemaValueType]) -> String {
+        let types = non_null_types(v_types);
+        let go_type = match types[..] {
+            [] => "interface{}".to_string(),
+            [v_type] => self.single_type(v_type),
+            _ => {
+                self.uses_json = true;
+                "json.RawMessage".to_string()
+            }
+        };
+
+        match is_nullable(v_types) && !is_nillable(&go_type) {
+            true => format!("*{}", go_type),
+            false => go_type,
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+            SchemaValueType::Primitive(_) => "interface{}".into(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "int64".into(),
+                IntegerWidth::U64 => "uint64".into(),
+                IntegerWidth::Wider => {
+                    self.uses_json = true;
+                    "json.Number".into()
+                }
+            },
+            SchemaValueType::Float(..) => "float64".into(),
+            SchemaValueType::String(..) => "string".into(),
+            SchemaValueType::Array(v_types) => format!("[]{}", self.go_type(v_types)),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+}
+
+fn is_nillable(go_type: &str) -> bool {
+    go_type.starts_with('*') || go_type.starts_with("[]") || go_type == "interface{}" || go_type == "json.RawMessage"
+}
+
+// Fields must be exported to be encoded, so names not starting with an uppercase letter,
+// like keys starting with a digit, are prefixed with `Field`
+fn field_name(key: &str) -> String {
+    let name = pascal_case(key);
+    match name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        true => name,
+        false => format!("Field{}", name.strip_prefix('_').unwrap_or(&name)),
+    }
+}
+
+// The names encoding/json accepts in tags, others are ignored and the field name is used instead
+fn is_valid_tag(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || "!#$%&()*+-./:;<=>?@[]^_{|}~ ".contains(c))
+}
+
+fn quote(value: &str) -> String {
+    serde_json::Value::String(value.into()).to_string()
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use super::{camel_case, is_nullable, non_null_types, pascal_case, unique_field_name, TypeNames};
+use crate::{Schema, SchemaValueType};
+
+// Built-in scalars and root operation types that generated types must not shadow
+const RESERVED: &[&str] = &["Boolean", "Float", "ID", "Int", "JSON", "Mutation", "Query", "String", "Subscription"];
+
+impl Schema {
+    /// Generates GraphQL SDL with a `type` for the schema and every schema nested in it.
+    ///
+    /// Fields are non-null (`!`) when the key was present in every object and never `NULL`,
+    /// and arrays become lists. Keys with several object types become a `union`, other keys
+    /// with several types use a custom `JSON` scalar. Integers are `Int` when they fit in 32
+    /// bits and `Float` otherwise.
+    pub fn to_graphql(&self) -> String {
+        let mut emitter = GraphQlEmitter {
+            names: TypeNames::new(self, RESERVED),
+            unions: Vec::new(),
+            uses_json: false,
+        };
+        let schemas = emitter
+            .names
+            .schemas()
+            .map(|(name, schema)| (name.to_string(), schema))
+            .collect::<Vec<(String, &Schema)>>();
+
+        let mut definitions = Vec::new();
+        // Object types need at least one field, empty objects use the `JSON` scalar instead
+        for (name, schema) in schemas
+            .into_iter()
+            .filter(|(_, schema)| !schema.map.is_empty())
+        {
+            definitions.push(emitter.object_type(&name, schema));
+            definitions.append(&mut emitter.unions);
+        }
+
+        if emitter.uses_json {
+            definitions.insert(0, "scalar JSON\n".into());
+        }
+        definitions.join("\n")
+    }
+}
+
+struct GraphQlEmitter<'a> {
+    names: TypeNames<'a>,
+    // Unions created for the fields of the current type
+    unions: Vec<String>,
+    uses_json: bool,
+}
+
+impl GraphQlEmitter<'_> {
+    fn object_type(&mut self, name: &str, schema: &Schema) -> String {
+        let mut sdl = format!("type {} {{\n", name);
+        let mut field_names = HashSet::new();
+        for (key, v_types) in &schema.map {
+            let mut field_type = self.field_type(v_types, &format!("{}{}", name, pascal_case(key)));
+            if !schema.is_required(key) {
+                field_type = field_type.trim_end_matches('!').to_string();
+            }
+            let field_name = unique_field_name(&mut field_names, field_name(key));
+            sdl.push_str(&format!("  {}: {}\n", field_name, field_type));
+        }
+        sdl.push_str("}\n");
+        sdl
+    }
+
+    // The type of a field or list item, non-null unless `NULL` was found
+    fn field_type(&mut self, v_types: &[SchemaValueType], union_name: &str) -> String {
+        let types = non_null_types(v_types);
+        let objects = types
+            .iter()
+            .filter_map(|v_type| match v_type {
+                SchemaValueType::Object(schema) if !schema.map.is_empty() => Some(schema),
+                _ => None,
+            })
+            .collect::<Vec<&Schema>>();
+
+        let field_type = match types[..] {
+            [] => self.json(),
+            [v_type] => self.single_type(v_type, union_name),
+            _ if objects.len() == types.len() => {
+                let name = self.names.unique(union_name);
+                let members = objects
+                    .iter()
+                    .map(|schema| self.names.name_of(schema))
+                    .collect::<Vec<&str>>()
+                    .join(" | ");
+                self.unions
+                    .push(format!("union {} = {}\n", name, members));
+                name
+            }
+            _ => self.json(),
+        };
+
+        match is_nullable(v_types) {
+            true => field_type,
+            false => format!("{}!", field_type),
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType, union_name: &str) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "Boolean".into(),
+            SchemaValueType::Primitive(_) => self.json(),
+            SchemaValueType::Integer(min, max) if i128::from(i32::MIN) <= *min && *max <= i128::from(i32::MAX) => "Int".into(),
+            SchemaValueType::Integer(..) | SchemaValueType::Float(..) => "Float".into(),
+            SchemaValueType::String(..) => "String".into(),
+            SchemaValueType::Array(v_types) => format!("[{}]", self.field_type(v_types, &format!("{}Item", union_name))),
+            SchemaValueType::Object(schema) if schema.map.is_empty() => self.json(),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn json(&mut self) -> String {
+        self.uses_json = true;
+        "JSON".into()
+    }
+}
+
+// Keys are kept when they are valid names, so the default resolvers find them
+fn field_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_name = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !key.starts_with("__");
+
+    match is_name {
+        true => key.into(),
+        false => camel_case(key),
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use super::{camel_case, integer_width, is_nullable, non_null_types, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaValueType};
+
+// Types from `java.lang` and the generated imports that generated records must not shadow
+const RESERVED: &[&str] = &[
+    "BigInteger",
+    "Boolean",
+    "Class",
+    "Double",
+    "Integer",
+    "JsonNode",
+    "JsonProperty",
+    "List",
+    "Long",
+    "Number",
+    "Object",
+    "Override",
+    "Record",
+    "String",
+];
+
+// Keywords, and the methods of `Object` that record components can't be named after
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "record",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+    "yield",
+    "clone",
+    "finalize",
+    "getClass",
+    "hashCode",
+    "notify",
+    "notifyAll",
+    "toString",
+    "wait",
+];
+
+impl Schema {
+    /// Generates Java 17 records for Jackson, with the records of nested schemas nested in the
+    /// record of the schema, so they fit in a single file.
+    ///
+    /// Components are camelCase, with `@JsonProperty` for the keys they don't match. Numbers
+    /// and booleans use their boxed types when the key can be `NULL` or is not present in every
+    /// object. Arrays become `List<T>` and keys with several types fall back to `JsonNode`.
+    pub fn to_java(&self) -> String {
+        let names = TypeNames::new(self, RESERVED);
+        let mut emitter = JavaEmitter {
+            names: &names,
+            uses_json_property: false,
+            uses_json_node: false,
+            uses_list: false,
+            uses_big_integer: false,
+        };
+
+        let mut records = names
+            .schemas()
+            .map(|(name, schema)| emitter.record(name, schema))
+            .collect::<Vec<String>>();
+        let root = records.remove(0);
+        let body = match records.is_empty() {
+            true => "{}".to_string(),
+            false => format!(
+                "{{\n{}}}",
+                records
+                    .iter()
+                    .map(|record| indent(&format!("{} {{}}\n", record)))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+        };
+
+        let mut imports = Vec::new();
+        if emitter.uses_json_property {
+            imports.push("import com.fasterxml.jackson.annotation.JsonProperty;\n");
+        }
+        if emitter.uses_json_node {
+            imports.push("import com.fasterxml.jackson.databind.JsonNode;\n");
+        }
+        if emitter.uses_big_integer {
+            imports.push("import java.math.BigInteger;\n");
+        }
+        if emitter.uses_list {
+            imports.push("import java.util.List;\n");
+        }
+        if !imports.is_empty() {
+            imports.push("\n");
+        }
+
+        format!("{}{} {}\n", imports.concat(), root, body)
+    }
+}
+
+struct JavaEmitter<'a> {
+    names: &'a TypeNames<'a>,
+    uses_json_property: bool,
+    uses_json_node: bool,
+    uses_list: bool,
+    uses_big_integer: bool,
+}
+
+impl JavaEmitter<'_> {
+    // The declaration of the record, without its body
+    fn record(&mut self, name: &str, schema: &Schema) -> String {
+        let mut component_names = HashSet::new();
+        let mut components = Vec::new();
+
+        for (key, v_types) in &schema.map {
+            let java_type = self.java_type(v_types, is_nullable(v_types) || !schema.is_required(key));
+            let component_name = unique_field_name(&mut component_names, component_name(key));
+            let annotation = match component_name == *key {
+                true => String::new(),
+                false => {
+                    self.uses_json_property = true;
+                    format!("@JsonProperty({}) ", serde_json::Value::String(key.clone()))
+                }
+            };
+            components.push(format!("    {}{} {}", annotation, java_type, component_name));
+        }
+
+        match components.is_empty() {
+            true => format!("public record {}()", name),
+            false => format!("public record {}(\n{}\n)", name, components.join(",\n")),
+        }
+    }
+
+    // Primitives are boxed when the value can be null, which is always the case for list items
+    fn java_type(&mut self, v_types: &[SchemaValueType], boxed: bool) -> String {
+        match non_null_types(v_types)[..] {
+            [v_type] => self.single_type(v_type, boxed || is_nullable(v_types)),
+            _ => self.json_node(),
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType, boxed: bool) -> String {
+        match (v_type, boxed) {
+            (SchemaValueType::Primitive(name), false) if name == "BOOL" => "boolean".into(),
+            (SchemaValueType::Primitive(name), true) if name == "BOOL" => "Boolean".into(),
+            (SchemaValueType::Primitive(_), _) => self.json_node(),
+            (SchemaValueType::Integer(min, max), boxed) => match (integer_width(*min, *max), boxed) {
+                (IntegerWidth::I64, false) => "long".into(),
+                (IntegerWidth::I64, true) => "Long".into(),
+                // Java has no unsigned types
+                (_, _) => {
+                    self.uses_big_integer = true;
+                    "BigInteger".into()
+                }
+            },
+            (SchemaValueType::Float(..), false) => "double".into(),
+            (SchemaValueType::Float(..), true) => "Double".into(),
+            (SchemaValueType::String(..), _) => "String".into(),
+            (SchemaValueType::Array(v_types), _) => {
+                self.uses_list = true;
+                format!("List<{}>", self.java_type(v_types, true))
+            }
+            (SchemaValueType::Object(schema), _) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn json_node(&mut self) -> String {
+        self.uses_json_node = true;
+        "JsonNode".into()
+    }
+}
+
+// Keywords and names of `Object` methods get a trailing underscore
+fn component_name(key: &str) -> String {
+    let name = camel_case(key);
+    match KEYWORDS.contains(&name.as_str()) {
+        true => format!("{}_", name),
+        false => name,
+    }
+}
+
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| format!("    {}\n", line))
+        .collect()
+}
@@ -0,0 +1,119 @@
+use serde_json::{json, Value as JsonValue};
+
+use super::{is_nullable, non_null_types};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+impl Schema {
+    /// Generates a JSON Type Definition (RFC 8927) schema.
+    ///
+    /// Keys that are always present are `properties`, the others `optionalProperties`. Keys
+    /// that can be `NULL` are `nullable` and arrays use `elements`. Integers are `int32` or
+    /// `uint32` when they fit, `float64` otherwise. Keys with several object shapes use a
+    /// `discriminator` when the shapes share a string key with different values in every
+    /// shape, other keys with several types use the empty form, which accepts any value.
+    /// Discriminators are only found in schemas built with `SchemaBuilder::track_strings`.
+    pub fn to_jtd(&self) -> JsonValue {
+        JsonValue::Object(properties(self, None))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates a JSON Type Definition for any root, see `Schema::to_jtd`.
+    /// Other roots than objects use the form of their values, e.g. `{ "elements": { "type": "string" } }`.
+    pub fn to_jtd(&self) -> JsonValue {
+        match self {
+            SchemaRoot::Object(schema) => schema.to_jtd(),
+            SchemaRoot::Value(v_types) => jtd(v_types),
+        }
+    }
+}
+
+// The properties form of a schema, without the tag key of a discriminator
+fn properties(schema: &Schema, tag: Option<&str>) -> serde_json::Map<String, JsonValue> {
+    let mut properties = serde_json::Map::new();
+    let mut optional_properties = serde_json::Map::new();
+
+    for (key, v_types) in schema
+        .map
+        .iter()
+        .filter(|(key, _)| Some(key.as_str()) != tag)
+    {
+        match schema.is_required(key) {
+            true => properties.insert(key.clone(), jtd(v_types)),
+            false => optional_properties.insert(key.clone(), jtd(v_types)),
+        };
+    }
+
+    let mut jtd = serde_json::Map::new();
+    // At least one of them is needed to tell the properties form apart from the empty form
+    if !properties.is_empty() || optional_properties.is_empty() {
+        jtd.insert("properties".into(), properties.into());
+    }
+    if !optional_properties.is_empty() {
+        jtd.insert("optionalProperties".into(), optional_properties.into());
+    }
+    jtd
+}
+
+fn jtd(v_types: &[SchemaValueType]) -> JsonValue {
+    let types = non_null_types(v_types);
+    let objects = types
+        .iter()
+        .filter_map(|v_type| match v_type {
+            SchemaValueType::Object(schema) => Some(schema),
+            _ => None,
+        })
+        .collect::<Vec<&Schema>>();
+
+    let mut jtd = match types[..] {
+        [] => json!({}),
+        [v_type] => single(v_type),
+        _ if objects.len() == types.len() => discriminator(&objects).unwrap_or_else(|| json!({})),
+        _ => json!({}),
+    };
+
+    // The empty form accepts `null` already
+    if is_nullable(v_types) && jtd != json!({}) {
+        jtd["nullable"] = true.into();
+    }
+    jtd
+}
+
+fn single(v_type: &SchemaValueType) -> JsonValue {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => json!({ "type": "boolean" }),
+        SchemaValueType::Primitive(_) => json!({}),
+        SchemaValueType::Integer(min, max) if i128::from(i32::MIN) <= *min && *max <= i128::from(i32::MAX) => json!({ "type": "int32" }),
+        SchemaValueType::Integer(min, max) if 0 <= *min && *max <= i128::from(u32::MAX) => json!({ "type": "uint32" }),
+        SchemaValueType::Integer(..) | SchemaValueType::Float(..) => json!({ "type": "float64" }),
+        SchemaValueType::String(..) => json!({ "type": "string" }),
+        SchemaValueType::Array(v_types) => json!({ "elements": jtd(v_types) }),
+        SchemaValueType::Object(schema) => JsonValue::Object(properties(schema, None)),
+    }
+}
+
+// The first key that is a string in every shape, with values that tell the shapes apart
+fn discriminator(shapes: &[&Schema]) -> Option<JsonValue> {
+    let tag = shapes[0].map.keys().find(|key| {
+        let mut seen = Vec::new();
+        shapes.iter().all(|shape| {
+            let is_string = matches!(shape.map.get(*key).map(Vec::as_slice), Some([SchemaValueType::String(..)]));
+            let values = shape.string_values.get(*key);
+            let is_tag = is_string && shape.is_required(key) && values.is_some_and(|values| values.iter().all(|value| !seen.contains(&value)));
+            seen.extend(values.into_iter().flatten());
+            is_tag
+        })
+    })?;
+
+    let mut mapping = serde_json::Map::new();
+    for shape in shapes {
+        for value in &shape.string_values[tag] {
+            mapping.insert(value.clone(), JsonValue::Object(properties(shape, Some(tag))));
+        }
+    }
+
+    Some(json!({
+        "discriminator": tag,
+        "mapping": mapping,
+    }))
+}
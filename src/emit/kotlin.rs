@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use super::{camel_case, integer_width, is_nullable, non_null_types, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+// Built-in and imported types that generated classes must not shadow
+const RESERVED: &[&str] = &[
+    "Any",
+    "Array",
+    "Boolean",
+    "Double",
+    "Int",
+    "JsonElement",
+    "List",
+    "Long",
+    "ULong",
+    "Map",
+    "Nothing",
+    "Pair",
+    "SerialName",
+    "Serializable",
+    "Set",
+    "String",
+    "Unit",
+];
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+impl Schema {
+    /// Generates Kotlin data classes for kotlinx.serialization for the schema and all schemas
+    /// nested in it.
+    ///
+    /// Keys that aren't valid identifiers are renamed with `@SerialName`. Keys that can be
+    /// `NULL` are nullable, keys that are not present in every object also default to `null`.
+    /// Arrays become `List<T>` and keys with several types fall back to `JsonElement`.
+    pub fn to_kotlin(&self) -> String {
+        kotlin(&TypeNames::new(self, RESERVED))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Kotlin data classes for any root, see `Schema::to_kotlin`.
+    /// Other roots than objects become a type alias, e.g. `typealias Root = List<Long>`.
+    pub fn to_kotlin(&self) -> String {
+        kotlin(&TypeNames::for_root(self, RESERVED))
+    }
+}
+
+fn kotlin(names: &TypeNames) -> String {
+    let mut emitter = KotlinEmitter {
+        names,
+        uses_serial_name: false,
+        uses_json_element: false,
+    };
+
+    let alias = names
+        .alias()
+        .map(|(name, v_types)| format!("typealias {} = {}\n", name, emitter.kotlin_type(v_types)));
+    let classes = alias
+        .into_iter()
+        .chain(
+            names
+                .schemas()
+                .map(|(name, schema)| emitter.class(name, schema)),
+        )
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let mut imports = Vec::new();
+    if emitter.uses_serial_name {
+        imports.push("import kotlinx.serialization.SerialName\n");
+    }
+    // Only classes are annotated, an alias of built-in types needs no imports
+    if names.schemas().next().is_some() {
+        imports.push("import kotlinx.serialization.Serializable\n");
+    }
+    if emitter.uses_json_element {
+        imports.push("import kotlinx.serialization.json.JsonElement\n");
+    }
+
+    match imports.is_empty() {
+        true => classes,
+        false => format!("{}\n{}", imports.concat(), classes),
+    }
+}
+
+struct KotlinEmitter<'a> {
+    names: &'a TypeNames<'a>,
+    uses_serial_name: bool,
+    uses_json_element: bool,
+}
+
+impl KotlinEmitter<'_> {
+    fn class(&mut self, name: &str, schema: &Schema) -> String {
+        // Data classes need at least one property
+        if schema.map.is_empty() {
+            return format!("@Serializable\nclass {}\n", name);
+        }
+
+        let mut property_names = HashSet::new();
+        let mut properties = Vec::new();
+        for (key, v_types) in &schema.map {
+            let mut kotlin_type = self.kotlin_type(v_types);
+            let default = match schema.is_required(key) {
+                true => "",
+                false => {
+                    if !kotlin_type.ends_with('?') {
+                        kotlin_type.push('?');
+                    }
+                    " = null"
+                }
+            };
+
+            let (property_name, is_renamed) = property_name(&mut property_names, key);
+            let serial_name = match is_renamed {
+                true => {
+                    self.uses_serial_name = true;
+                    format!("@SerialName({}) ", serde_json::Value::String(key.clone()))
+                }
+                false => String::new(),
+            };
+            properties.push(format!("    {}val {}: {}{}", serial_name, property_name, kotlin_type, default));
+        }
+
+        format!("@Serializable\ndata class {}(\n{},\n)\n", name, properties.join(",\n"))
+    }
+
+    fn kotlin_type(&mut self, v_types: &[SchemaValueType]) -> String {
+        let kotlin_type = match non_null_types(v_types)[..] {
+            [v_type] => self.single_type(v_type),
+            _ => self.json_element(),
+        };
+
+        match is_nullable(v_types) {
+            true => format!("{}?", kotlin_type),
+            false => kotlin_type,
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "Boolean".into(),
+            SchemaValueType::Primitive(_) => self.json_element(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "Long".into(),
+                IntegerWidth::U64 => "ULong".into(),
+                IntegerWidth::Wider => self.json_element(),
+            },
+            SchemaValueType::Float(..) => "Double".into(),
+            SchemaValueType::String(..) => "String".into(),
+            SchemaValueType::Array(v_types) => format!("List<{}>", self.kotlin_type(v_types)),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn json_element(&mut self) -> String {
+        self.uses_json_element = true;
+        "JsonElement".into()
+    }
+}
+
+// Keys that are valid identifiers are kept, keywords are escaped with backticks.
+// Whether the property was renamed is returned along with its name.
+fn property_name(used: &mut HashSet<String>, key: &str) -> (String, bool) {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+
+    let base = match is_identifier {
+        true => key.to_string(),
+        false => camel_case(key),
+    };
+    let name = unique_field_name(used, base);
+    let is_renamed = name != key;
+
+    match KEYWORDS.contains(&name.as_str()) {
+        true => (format!("`{}`", name), is_renamed),
+        false => (name, is_renamed),
+    }
+}
@@ -0,0 +1,360 @@
+//! Emitters that turn an inferred `Schema` into type definitions for other languages.
+//!
+//! Every emitter adds a `to_*` method to `Schema`, next to `to_json` and `to_json_schema`.
+//! Emitters that can describe other roots than objects, e.g. with a type alias, add one to `SchemaRoot` too.
+
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+mod arrow;
+mod avro;
+mod bigquery;
+mod csharp;
+mod go;
+mod graphql;
+mod java;
+mod jtd;
+mod kotlin;
+mod protobuf;
+mod python;
+mod rust;
+mod spark;
+mod sql;
+mod swift;
+mod typescript;
+mod zod;
+
+pub use python::PythonStyle;
+pub use sql::SqlDialect;
+
+// Unique type names for a schema and all schemas nested in it, in the order they are found in
+pub(crate) struct TypeNames<'a> {
+    schemas: Vec<(String, &'a Schema)>,
+    indices: HashMap<*const Schema, usize>,
+    used: HashSet<String>,
+    alias: Option<(String, &'a [SchemaValueType])>,
+}
+
+impl<'a> TypeNames<'a> {
+    // Names are derived from `Schema::name`, `reserved` names (e.g. built-in types) are never used
+    pub(crate) fn new(root: &'a Schema, reserved: &[&str]) -> Self {
+        let mut names = Self::empty(reserved);
+        names.add(root);
+        names
+    }
+
+    // Like `new`, for any root. Other roots than objects are named `Root`, for a type alias
+    // that emitters declare from `alias`, and the objects in them are named as usual.
+    pub(crate) fn for_root(root: &'a SchemaRoot, reserved: &[&str]) -> Self {
+        match root {
+            SchemaRoot::Object(schema) => Self::new(schema, reserved),
+            SchemaRoot::Value(v_types) => {
+                let mut names = Self::empty(reserved);
+                let name = names.unique("Root");
+                names.add_nested(v_types);
+                names.alias = Some((name, v_types));
+                names
+            }
+        }
+    }
+
+    fn empty(reserved: &[&str]) -> Self {
+        Self {
+            schemas: Vec::new(),
+            indices: HashMap::new(),
+            used: reserved
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            alias: None,
+        }
+    }
+
+    fn add(&mut self, schema: &'a Schema) {
+        let name = self.unique(&pascal_case(&schema.name));
+        self.indices
+            .insert(schema as *const Schema, self.schemas.len());
+        self.schemas.push((name, schema));
+
+        for v_types in schema.map.values() {
+            self.add_nested(v_types);
+        }
+    }
+
+    fn add_nested(&mut self, v_types: &'a [SchemaValueType]) {
+        for v_type in v_types {
+            match v_type {
+                SchemaValueType::Object(schema) => self.add(schema),
+                SchemaValueType::Array(v_types) => self.add_nested(v_types),
+                _ => {}
+            }
+        }
+    }
+
+    // Every schema with its name, parents before the schemas nested in them
+    pub(crate) fn schemas(&self) -> impl DoubleEndedIterator<Item = (&str, &'a Schema)> + '_ {
+        self.schemas
+            .iter()
+            .map(|(name, schema)| (name.as_str(), *schema))
+    }
+
+    // The name and types of a root that isn't an object, see `for_root`
+    pub(crate) fn alias(&self) -> Option<(&str, &'a [SchemaValueType])> {
+        self.alias
+            .as_ref()
+            .map(|(name, v_types)| (name.as_str(), *v_types))
+    }
+
+    pub(crate) fn name_of(&self, schema: &Schema) -> &str {
+        &self.schemas[self.indices[&(schema as *const Schema)]].0
+    }
+
+    // Reserves a name that is not used yet by appending a number to `base` if needed
+    pub(crate) fn unique(&mut self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut suffix = 2;
+        while self.used.contains(&name) {
+            name = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+        self.used.insert(name.clone());
+        name
+    }
+}
+
+// Splits a key into words at non-alphanumeric characters and at case changes, e.g. `mobilePhone` or `mobile_phone`
+pub(crate) fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars = name.chars().collect::<Vec<char>>();
+
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+        let next = chars.get(i + 1);
+        let is_boundary = match prev {
+            Some(prev) if c.is_uppercase() => prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next.is_some_and(|next| next.is_lowercase())),
+            _ => false,
+        };
+        if is_boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(*c);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+// Identifiers can't start with a digit, so those are prefixed with an underscore
+fn ensure_identifier(name: String, fallback: &str) -> String {
+    match name.chars().next() {
+        None => fallback.to_string(),
+        Some(first) if first.is_numeric() => format!("_{}", name),
+        Some(_) => name,
+    }
+}
+
+pub(crate) fn pascal_case(name: &str) -> String {
+    let name = words(name)
+        .iter()
+        .map(|word| capitalize(word))
+        .collect::<String>();
+    ensure_identifier(name, "Type")
+}
+
+pub(crate) fn camel_case(name: &str) -> String {
+    let name = words(name)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| match i {
+            0 => word.to_lowercase(),
+            _ => capitalize(word),
+        })
+        .collect::<String>();
+    ensure_identifier(name, "field")
+}
+
+pub(crate) fn snake_case(name: &str) -> String {
+    let name = words(name)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<String>>()
+        .join("_");
+    ensure_identifier(name, "field")
+}
+
+// Field names are unique per type, clashes like `fooBar` and `foo_bar` get a numbered suffix
+pub(crate) fn unique_field_name(used: &mut HashSet<String>, base: String) -> String {
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while used.contains(&name) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+pub(crate) fn is_nullable(v_types: &[SchemaValueType]) -> bool {
+    v_types
+        .iter()
+        .any(|v_type| matches!(v_type, SchemaValueType::Primitive(name) if name == "NULL"))
+}
+
+// The types without `NULL`. Integers are left out when floats were found too,
+// as most languages represent both with a single floating point type.
+pub(crate) fn non_null_types(v_types: &[SchemaValueType]) -> Vec<&SchemaValueType> {
+    let has_float = v_types
+        .iter()
+        .any(|v_type| matches!(v_type, SchemaValueType::Float(..)));
+
+    v_types
+        .iter()
+        .filter(|v_type| match v_type {
+            SchemaValueType::Primitive(name) => name != "NULL",
+            SchemaValueType::Integer(..) => !has_float,
+            _ => true,
+        })
+        .collect()
+}
+
+// The types with the objects ordered by their number of keys, most first, and the other types
+// in place. Unions that decode into the first variant a value matches, and drop the keys it
+// doesn't know, would otherwise pick an object with a subset of the keys of the value.
+pub(crate) fn most_specific_first<'a>(types: &[&'a SchemaValueType]) -> Vec<&'a SchemaValueType> {
+    let mut objects = types
+        .iter()
+        .filter(|v_type| matches!(v_type, SchemaValueType::Object(_)))
+        .copied()
+        .collect::<Vec<&SchemaValueType>>();
+    objects.sort_by_key(|v_type| match v_type {
+        SchemaValueType::Object(schema) => std::cmp::Reverse(schema.map.len()),
+        _ => std::cmp::Reverse(0),
+    });
+
+    let mut objects = objects.into_iter();
+    types
+        .iter()
+        .map(|v_type| match v_type {
+            SchemaValueType::Object(_) => objects.next().unwrap_or(v_type),
+            _ => v_type,
+        })
+        .collect()
+}
+
+// The keys of several object shapes with their types merged, and whether every shape has them
+pub(crate) fn merged_keys<'a>(shapes: &[&'a Schema]) -> IndexMap<&'a str, (Vec<SchemaValueType>, bool)> {
+    let mut keys: IndexMap<&str, (Vec<SchemaValueType>, bool)> = IndexMap::new();
+    for shape in shapes {
+        for (key, v_types) in &shape.map {
+            keys.entry(key)
+                .or_insert_with(|| (Vec::new(), shapes.iter().all(|shape| shape.is_required(key))))
+                .0
+                .extend(v_types.iter().cloned());
+        }
+    }
+    keys
+}
+
+// The smallest common integer type the integers of a key fit into. Integers above `i64::MAX`
+// only fit into an unsigned type, and into neither of them when negative integers were found too.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum IntegerWidth {
+    I64,
+    U64,
+    Wider,
+}
+
+pub(crate) fn integer_width(min: i128, max: i128) -> IntegerWidth {
+    match (min, max) {
+        (min, max) if i128::from(i64::MIN) <= min && max <= i128::from(i64::MAX) => IntegerWidth::I64,
+        (min, max) if 0 <= min && max <= i128::from(u64::MAX) => IntegerWidth::U64,
+        _ => IntegerWidth::Wider,
+    }
+}
+
+// The single kind of value of a key, for formats without unions. The items of its arrays
+// and the shapes of its objects are merged, other types that can't be merged are `Mixed`.
+pub(crate) enum ValueKind<'a> {
+    Null,
+    Bool,
+    Integer(IntegerWidth),
+    Float,
+    String,
+    Array(Vec<SchemaValueType>),
+    Object(Vec<&'a Schema>),
+    Mixed,
+}
+
+pub(crate) fn value_kind(v_types: &[SchemaValueType]) -> ValueKind<'_> {
+    let types = non_null_types(v_types);
+    let all = |f: fn(&SchemaValueType) -> bool| types.iter().all(|v_type| f(v_type));
+
+    if types.is_empty() {
+        return ValueKind::Null;
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Primitive(name) if name == "BOOL")) {
+        return ValueKind::Bool;
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Integer(..))) {
+        let (min, max) = types
+            .iter()
+            .filter_map(|v_type| match v_type {
+                SchemaValueType::Integer(min, max) => Some((*min, *max)),
+                _ => None,
+            })
+            .fold((i128::MAX, i128::MIN), |(min, max), (v_min, v_max)| (min.min(v_min), max.max(v_max)));
+        return ValueKind::Integer(integer_width(min, max));
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Float(..))) {
+        return ValueKind::Float;
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::String(..))) {
+        return ValueKind::String;
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Array(_))) {
+        let items = types
+            .iter()
+            .filter_map(|v_type| match v_type {
+                SchemaValueType::Array(items) => Some(items.iter().cloned()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        return ValueKind::Array(items);
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Object(_))) {
+        let shapes = types
+            .iter()
+            .filter_map(|v_type| match v_type {
+                SchemaValueType::Object(schema) => Some(schema),
+                _ => None,
+            })
+            .collect();
+        return ValueKind::Object(shapes);
+    }
+    ValueKind::Mixed
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+
+use super::{integer_width, is_nullable, non_null_types, pascal_case, snake_case, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaValueType};
+
+impl Schema {
+    /// Generates proto3 definitions with a `message` for the schema and every schema nested in it.
+    ///
+    /// Fields are numbered in key order. Keys that can be `NULL` or are not present in every
+    /// object are `optional`, arrays are `repeated` and keys with several types become a `oneof`.
+    /// As `repeated` fields can't be nested or be part of a `oneof`, arrays of several types and
+    /// arrays of arrays get a wrapper message, e.g. `RootPhonesItem`. `NULL` items of arrays are
+    /// left out, and types that aren't known use `google.protobuf.Value`.
+    pub fn to_protobuf(&self) -> String {
+        let mut emitter = ProtoEmitter {
+            names: TypeNames::new(self, &[]),
+            wrappers: Vec::new(),
+            uses_value: false,
+        };
+        let schemas = emitter
+            .names
+            .schemas()
+            .map(|(name, schema)| (name.to_string(), schema))
+            .collect::<Vec<(String, &Schema)>>();
+
+        let mut messages = Vec::new();
+        for (name, schema) in schemas {
+            messages.push(emitter.message(&name, schema));
+            messages.append(&mut emitter.wrappers);
+        }
+
+        let mut proto = String::from("syntax = \"proto3\";\n\npackage schema;\n\n");
+        if emitter.uses_value {
+            proto.push_str("import \"google/protobuf/struct.proto\";\n\n");
+        }
+        proto.push_str(&messages.join("\n"));
+        proto
+    }
+}
+
+struct ProtoEmitter<'a> {
+    names: TypeNames<'a>,
+    // Messages created for the fields of the current message
+    wrappers: Vec<String>,
+    uses_value: bool,
+}
+
+impl ProtoEmitter<'_> {
+    fn message(&mut self, name: &str, schema: &Schema) -> String {
+        let mut field_names = HashSet::new();
+        let mut number = 1;
+        let mut proto = format!("message {} {{\n", name);
+
+        for (key, v_types) in &schema.map {
+            let field_name = unique_field_name(&mut field_names, field_name(key));
+            let base = format!("{}{}", name, pascal_case(key));
+            let types = non_null_types(v_types);
+
+            match types[..] {
+                [] => proto.push_str(&field(&self.value(), &field_name, number, key)),
+                [SchemaValueType::Array(items)] => {
+                    let item_type = self.item_type(items, &format!("{}Item", base));
+                    proto.push_str(&field(&format!("repeated {}", item_type), &field_name, number, key));
+                }
+                [v_type] => {
+                    let proto_type = self.singular_type(v_type, &base);
+                    let proto_type = match is_nullable(v_types) || !schema.is_required(key) {
+                        true => format!("optional {}", proto_type),
+                        false => proto_type,
+                    };
+                    proto.push_str(&field(&proto_type, &field_name, number, key));
+                }
+                _ => {
+                    proto.push_str(&self.oneof(&field_name, &types, &base, &mut number, &mut field_names));
+                    continue;
+                }
+            }
+            number += 1;
+        }
+
+        proto.push_str("}\n");
+        proto
+    }
+
+    // A `oneof` with a field for each type, the fields are numbered from `number` on
+    fn oneof(&mut self, name: &str, types: &[&SchemaValueType], base: &str, number: &mut usize, field_names: &mut HashSet<String>) -> String {
+        let mut proto = format!("  oneof {} {{\n", name);
+        for v_type in types {
+            let proto_type = self.singular_type(v_type, base);
+            let field_name = unique_field_name(field_names, format!("{}_{}", name, type_suffix(v_type, &proto_type)));
+            proto.push_str(&format!("  {}", field(&proto_type, &field_name, *number, &field_name)));
+            *number += 1;
+        }
+        proto.push_str("  }\n");
+        proto
+    }
+
+    // The type of the items of a `repeated` field
+    fn item_type(&mut self, v_types: &[SchemaValueType], base: &str) -> String {
+        match non_null_types(v_types)[..] {
+            [] => self.value(),
+            [v_type] => self.singular_type(v_type, base),
+            ref types => {
+                let name = self.names.unique(base);
+                let mut field_names = HashSet::new();
+                let oneof = self.oneof("value", types, &name, &mut 1, &mut field_names);
+                self.wrappers
+                    .push(format!("message {} {{\n{}}}\n", name, oneof));
+                name
+            }
+        }
+    }
+
+    // Arrays that can't be `repeated` directly are wrapped in a message named `base` + `List`
+    fn singular_type(&mut self, v_type: &SchemaValueType, base: &str) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+            SchemaValueType::Primitive(_) => self.value(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "int64".into(),
+                IntegerWidth::U64 => "uint64".into(),
+                IntegerWidth::Wider => "double".into(),
+            },
+            SchemaValueType::Float(..) => "double".into(),
+            SchemaValueType::String(..) => "string".into(),
+            SchemaValueType::Array(v_types) => {
+                let name = self.names.unique(&format!("{}List", base));
+                let item_type = self.item_type(v_types, &format!("{}Item", name));
+                self.wrappers
+                    .push(format!("message {} {{\n  repeated {} values = 1;\n}}\n", name, item_type));
+                name
+            }
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn value(&mut self) -> String {
+        self.uses_value = true;
+        "google.protobuf.Value".into()
+    }
+}
+
+// Keys that differ from the field name are kept as its JSON name
+fn field(proto_type: &str, field_name: &str, number: usize, key: &str) -> String {
+    match field_name == key {
+        true => format!("  {} {} = {};\n", proto_type, field_name, number),
+        false => format!("  {} {} = {} [json_name = {}];\n", proto_type, field_name, number, serde_json::Value::String(key.into())),
+    }
+}
+
+fn type_suffix(v_type: &SchemaValueType, proto_type: &str) -> String {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+        SchemaValueType::Primitive(_) => "value".into(),
+        SchemaValueType::Integer(..) => "integer".into(),
+        SchemaValueType::Float(..) => "float".into(),
+        SchemaValueType::String(..) => "string".into(),
+        SchemaValueType::Array(_) => "list".into(),
+        SchemaValueType::Object(_) => snake_case(proto_type),
+    }
+}
+
+// Field names have to start with a letter, so keys starting with a digit are prefixed with `field`
+fn field_name(key: &str) -> String {
+    let name = snake_case(key);
+    match name.starts_with('_') {
+        true => format!("field{}", name),
+        false => name,
+    }
+}
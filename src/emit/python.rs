@@ -0,0 +1,255 @@
+use std::collections::{BTreeSet, HashSet};
+
+use super::{is_nullable, non_null_types, snake_case, unique_field_name, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+// Names used by the generated imports and constants that generated classes must not shadow
+const RESERVED: &[&str] = &["Any", "BaseModel", "ConfigDict", "False", "Field", "None", "Optional", "True", "Union"];
+
+const KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+// Names used in annotations, a field with a default would shadow them in the rest of the class body
+const SHADOWED: &[&str] = &["bool", "constr", "field", "float", "int", "list", "str"];
+
+// The prefix of the attributes of Pydantic models, which fields must not use
+const PROTECTED: &str = "model_";
+
+/// The kind of classes generated by `Schema::to_python`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PythonStyle {
+    /// Pydantic v2 models, with string lengths validated by `constr`
+    Pydantic,
+    /// Standard library dataclasses, which don't validate their fields
+    Dataclass,
+}
+
+impl Schema {
+    /// Generates Python classes for the schema and all schemas nested in it, either
+    /// Pydantic models or dataclasses depending on `style`.
+    ///
+    /// Nested classes come before the classes using them. Keys that can be `NULL` are
+    /// `Optional`, keys that are not present in every object default to `None`, arrays
+    /// become `list[T]` and keys with several types become `Union[A, B]`.
+    pub fn to_python(&self, style: PythonStyle) -> String {
+        python(&TypeNames::new(self, RESERVED), style)
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Python classes for any root, see `Schema::to_python`. Other roots than objects
+    /// become a type alias after the classes, e.g. `Root = list[Union[Item, int]]`.
+    pub fn to_python(&self, style: PythonStyle) -> String {
+        python(&TypeNames::for_root(self, RESERVED), style)
+    }
+}
+
+fn python(names: &TypeNames, style: PythonStyle) -> String {
+    let mut emitter = PythonEmitter {
+        names,
+        style,
+        imports: Imports::default(),
+    };
+
+    let mut blocks = names
+        .schemas()
+        .rev()
+        .map(|(name, schema)| emitter.class(name, schema))
+        .collect::<Vec<String>>();
+    if let Some((name, v_types)) = names.alias() {
+        blocks.push(format!("{} = {}\n", name, emitter.union(v_types)));
+    }
+
+    // An alias of built-in types alone needs no imports
+    let imports = emitter.imports.to_string();
+    match imports.is_empty() {
+        true => blocks.join("\n\n"),
+        false => format!("{}\n\n{}", imports, blocks.join("\n\n")),
+    }
+}
+
+// The names imported from each module, only the ones that are used
+#[derive(Default)]
+struct Imports {
+    dataclasses: BTreeSet<&'static str>,
+    typing: BTreeSet<&'static str>,
+    pydantic: BTreeSet<&'static str>,
+}
+
+impl std::fmt::Display for Imports {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let standard = [("dataclasses", &self.dataclasses), ("typing", &self.typing)];
+        let third_party = [("pydantic", &self.pydantic)];
+
+        let groups = [&standard[..], &third_party[..]]
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .filter(|(_, names)| !names.is_empty())
+                    .map(|(module, names)| {
+                        format!(
+                            "from {} import {}\n",
+                            module,
+                            names
+                                .iter()
+                                .copied()
+                                .collect::<Vec<&str>>()
+                                .join(", ")
+                        )
+                    })
+                    .collect::<String>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", groups.join("\n"))
+    }
+}
+
+struct PythonEmitter<'a> {
+    names: &'a TypeNames<'a>,
+    style: PythonStyle,
+    imports: Imports,
+}
+
+impl PythonEmitter<'_> {
+    fn class(&mut self, name: &str, schema: &Schema) -> String {
+        let mut class = match self.style {
+            PythonStyle::Pydantic => {
+                self.imports.pydantic.insert("BaseModel");
+                format!("class {}(BaseModel):\n", name)
+            }
+            PythonStyle::Dataclass => {
+                self.imports.dataclasses.insert("dataclass");
+                // Keyword-only, so fields with a default don't have to come last
+                format!("@dataclass(kw_only=True)\nclass {}:\n", name)
+            }
+        };
+
+        let mut fields = String::new();
+        let mut field_names = HashSet::new();
+        for (key, v_types) in &schema.map {
+            let mut py_type = self.union(v_types);
+            let required = schema.is_required(key);
+            if !required && !is_nullable(v_types) {
+                py_type = self.optional(&py_type);
+            }
+
+            let field_name = unique_field_name(&mut field_names, field_name(key, self.style));
+            let alias = match field_name == *key {
+                true => None,
+                false => Some(serde_json::Value::String(key.clone()).to_string()),
+            };
+            fields.push_str(&format!("    {}: {}{}\n", field_name, py_type, self.default(required, alias)));
+        }
+
+        // Renamed fields still start with `model_`, which Pydantic warns about unless allowed
+        if self.style == PythonStyle::Pydantic
+            && field_names
+                .iter()
+                .any(|name| name.starts_with(PROTECTED))
+        {
+            self.imports.pydantic.insert("ConfigDict");
+            class.push_str("    model_config = ConfigDict(protected_namespaces=())\n\n");
+        }
+        class.push_str(&fields);
+
+        if schema.map.is_empty() {
+            class.push_str("    pass\n");
+        }
+        class
+    }
+
+    // The default value of a field, which also carries the key of renamed fields
+    fn default(&mut self, required: bool, alias: Option<String>) -> String {
+        match (self.style, required, alias) {
+            (_, true, None) => String::new(),
+            (_, false, None) => " = None".into(),
+            (PythonStyle::Pydantic, true, Some(alias)) => {
+                self.imports.pydantic.insert("Field");
+                format!(" = Field(alias={})", alias)
+            }
+            (PythonStyle::Pydantic, false, Some(alias)) => {
+                self.imports.pydantic.insert("Field");
+                format!(" = Field(default=None, alias={})", alias)
+            }
+            (PythonStyle::Dataclass, true, Some(alias)) => {
+                self.imports.dataclasses.insert("field");
+                format!(" = field(metadata={{\"alias\": {}}})", alias)
+            }
+            (PythonStyle::Dataclass, false, Some(alias)) => {
+                self.imports.dataclasses.insert("field");
+                format!(" = field(default=None, metadata={{\"alias\": {}}})", alias)
+            }
+        }
+    }
+
+    fn optional(&mut self, py_type: &str) -> String {
+        self.imports.typing.insert("Optional");
+        format!("Optional[{}]", py_type)
+    }
+
+    fn union(&mut self, v_types: &[SchemaValueType]) -> String {
+        let mut types = Vec::new();
+        for v_type in non_null_types(v_types) {
+            let py_type = self.py_type(v_type);
+            if !types.contains(&py_type) {
+                types.push(py_type);
+            }
+        }
+
+        let py_type = match types.len() {
+            0 if is_nullable(v_types) => return "None".into(),
+            0 => self.any(),
+            1 => types.remove(0),
+            _ => {
+                self.imports.typing.insert("Union");
+                format!("Union[{}]", types.join(", "))
+            }
+        };
+
+        match is_nullable(v_types) {
+            true => self.optional(&py_type),
+            false => py_type,
+        }
+    }
+
+    fn py_type(&mut self, v_type: &SchemaValueType) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+            SchemaValueType::Primitive(_) => self.any(),
+            SchemaValueType::Integer(..) => "int".into(),
+            SchemaValueType::Float(..) => "float".into(),
+            SchemaValueType::String(min, max) => match self.style {
+                PythonStyle::Pydantic => {
+                    self.imports.pydantic.insert("constr");
+                    format!("constr(min_length={}, max_length={})", min, max)
+                }
+                PythonStyle::Dataclass => "str".into(),
+            },
+            SchemaValueType::Array(v_types) => format!("list[{}]", self.union(v_types)),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    fn any(&mut self) -> String {
+        self.imports.typing.insert("Any");
+        "Any".into()
+    }
+}
+
+// Keywords and shadowed names get a trailing underscore, as do the names Pydantic keeps for the
+// attributes of its models, like `model_config`. Names with a leading underscore are private in
+// Pydantic, so keys starting with a digit are prefixed with `field` instead.
+fn field_name(key: &str, style: PythonStyle) -> String {
+    let name = snake_case(key);
+    match name.as_str() {
+        _ if KEYWORDS.contains(&name.as_str()) || SHADOWED.contains(&name.as_str()) => format!("{}_", name),
+        _ if style == PythonStyle::Pydantic && name.starts_with(PROTECTED) => format!("{}_", name),
+        _ if name.starts_with('_') => format!("field{}", name),
+        _ => name,
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use super::{integer_width, is_nullable, most_specific_first, non_null_types, pascal_case, snake_case, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+// Types from the prelude and the generated imports that generated types must not shadow
+const RESERVED: &[&str] = &["Box", "Deserialize", "Option", "Result", "Self", "Serialize", "String", "Value", "Vec"];
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+impl Schema {
+    /// Generates Rust structs deriving serde's `Serialize` and `Deserialize` for the schema
+    /// and all schemas nested in it.
+    ///
+    /// Keys that can be `NULL` or are not present in every object become `Option<T>`,
+    /// arrays become `Vec<T>` and keys with several types become `#[serde(untagged)]` enums.
+    pub fn to_rust(&self) -> String {
+        rust(TypeNames::new(self, RESERVED))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Rust types for any root, see `Schema::to_rust`.
+    /// Other roots than objects become a type alias, e.g. `pub type Root = Vec<RootValueItem>;`
+    pub fn to_rust(&self) -> String {
+        rust(TypeNames::for_root(self, RESERVED))
+    }
+}
+
+fn rust(names: TypeNames) -> String {
+    let alias = names
+        .alias()
+        .map(|(name, v_types)| (name.to_string(), v_types));
+    let schemas = names
+        .schemas()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect::<Vec<(String, &Schema)>>();
+    let mut emitter = RustEmitter { names };
+
+    let mut rust = String::from("use serde::{Deserialize, Serialize};\n");
+    if let Some((name, v_types)) = alias {
+        rust.push('\n');
+        rust.push_str(&emitter.alias(&name, v_types));
+    }
+    for (name, schema) in schemas {
+        rust.push('\n');
+        rust.push_str(&emitter.structure(&name, schema));
+    }
+    rust
+}
+
+struct RustEmitter<'a> {
+    names: TypeNames<'a>,
+}
+
+impl RustEmitter<'_> {
+    // The struct, followed by the enums created for its fields
+    fn structure(&mut self, name: &str, schema: &Schema) -> String {
+        let mut fields = String::new();
+        let mut enums = Vec::new();
+        let mut field_names = HashSet::new();
+
+        for (key, v_types) in &schema.map {
+            let mut rust_type = self.union(v_types, &format!("{}{}", name, pascal_case(key)), &mut enums);
+            if !schema.is_required(key) && !is_nullable(v_types) {
+                rust_type = format!("Option<{}>", rust_type);
+            }
+
+            let field_name = unique_field_name(&mut field_names, field_name(key));
+            if field_name.trim_start_matches("r#") != key {
+                fields.push_str(&format!("    #[serde(rename = {:?})]\n", key));
+            }
+            fields.push_str(&format!("    pub {}: {},\n", field_name, rust_type));
+        }
+
+        let mut rust = format!("#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n", name, fields);
+        for enumeration in enums {
+            rust.push('\n');
+            rust.push_str(&enumeration);
+        }
+        rust
+    }
+
+    // The type alias, followed by the enums created for it
+    fn alias(&mut self, name: &str, v_types: &[SchemaValueType]) -> String {
+        let mut enums = Vec::new();
+        let mut rust = format!("pub type {} = {};\n", name, self.union(v_types, &format!("{}Value", name), &mut enums));
+        for enumeration in enums {
+            rust.push('\n');
+            rust.push_str(&enumeration);
+        }
+        rust
+    }
+
+    // A single type is used as-is, several types become an untagged enum called `enum_name`
+    fn union(&mut self, v_types: &[SchemaValueType], enum_name: &str, enums: &mut Vec<String>) -> String {
+        let types = non_null_types(v_types);
+        let rust_type = match types[..] {
+            [] => "serde_json::Value".to_string(),
+            [v_type] => self.rust_type(v_type, enum_name, enums),
+            _ => {
+                let enum_name = self.names.unique(enum_name);
+                let mut variants = String::new();
+                // Untagged enums deserialize into the first variant that matches
+                for v_type in most_specific_first(&types) {
+                    let rust_type = self.rust_type(v_type, &format!("{}Item", enum_name), enums);
+                    variants.push_str(&format!("    {}({}),\n", variant_name(v_type, &rust_type), rust_type));
+                }
+                enums.push(format!(
+                    "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(untagged)]\npub enum {} {{\n{}}}\n",
+                    enum_name, variants
+                ));
+                enum_name
+            }
+        };
+
+        match is_nullable(v_types) {
+            true => format!("Option<{}>", rust_type),
+            false => rust_type,
+        }
+    }
+
+    fn rust_type(&mut self, v_type: &SchemaValueType, enum_name: &str, enums: &mut Vec<String>) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+            SchemaValueType::Primitive(_) => "serde_json::Value".into(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "i64".into(),
+                IntegerWidth::U64 => "u64".into(),
+                IntegerWidth::Wider => "i128".into(),
+            },
+            SchemaValueType::Float(..) => "f64".into(),
+            SchemaValueType::String(..) => "String".into(),
+            SchemaValueType::Array(v_types) => format!("Vec<{}>", self.union(v_types, &format!("{}Item", enum_name), enums)),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+}
+
+fn variant_name(v_type: &SchemaValueType, rust_type: &str) -> String {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => "Bool".into(),
+        SchemaValueType::Primitive(_) => "Value".into(),
+        SchemaValueType::Integer(..) => "Integer".into(),
+        SchemaValueType::Float(..) => "Float".into(),
+        SchemaValueType::String(..) => "String".into(),
+        SchemaValueType::Array(_) => "Array".into(),
+        SchemaValueType::Object(_) => rust_type.into(),
+    }
+}
+
+// Keywords are escaped as raw identifiers, which serde serializes without the `r#` prefix
+fn field_name(key: &str) -> String {
+    let name = snake_case(key);
+    match name.as_str() {
+        "self" | "super" | "crate" => format!("{}_", name),
+        _ if KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
@@ -0,0 +1,51 @@
+use serde_json::{json, Value as JsonValue};
+
+use super::{is_nullable, merged_keys, value_kind, IntegerWidth, ValueKind};
+use crate::Schema;
+
+impl Schema {
+    /// Generates a Spark `StructType` in its JSON form, as read by `DataType.fromJson`.
+    ///
+    /// Objects become `struct`s, with the shapes of a key merged into one, and arrays become
+    /// `array`s. Fields are nullable when `NULL` was found or the key is missing in some objects.
+    /// Spark has no unions, so keys with several types, or only `NULL`, are `string`s, which
+    /// Spark's JSON reader fills with the values as JSON text.
+    pub fn to_spark(&self) -> JsonValue {
+        struct_type(&[self])
+    }
+}
+
+fn struct_type(shapes: &[&Schema]) -> JsonValue {
+    let fields = merged_keys(shapes)
+        .into_iter()
+        .map(|(key, (v_types, is_required))| {
+            json!({
+                "name": key,
+                "type": spark_type(value_kind(&v_types)),
+                "nullable": !is_required || is_nullable(&v_types),
+                "metadata": {},
+            })
+        })
+        .collect::<Vec<JsonValue>>();
+
+    json!({
+        "type": "struct",
+        "fields": fields,
+    })
+}
+
+fn spark_type(kind: ValueKind) -> JsonValue {
+    match kind {
+        ValueKind::Bool => "boolean".into(),
+        ValueKind::Integer(IntegerWidth::I64) => "long".into(),
+        ValueKind::Integer(_) => "decimal(20,0)".into(),
+        ValueKind::Float => "double".into(),
+        ValueKind::String | ValueKind::Null | ValueKind::Mixed => "string".into(),
+        ValueKind::Array(items) => json!({
+            "type": "array",
+            "elementType": spark_type(value_kind(&items)),
+            "containsNull": is_nullable(&items),
+        }),
+        ValueKind::Object(shapes) => struct_type(&shapes),
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::{HashSet, VecDeque};
+
+use indexmap::IndexMap;
+
+use super::{is_nullable, non_null_types, snake_case, unique_field_name, value_kind, IntegerWidth, ValueKind};
+use crate::{Schema, SchemaValueType};
+
+// Words that have to be quoted to be used as table or column names, including all the
+// reserved key words of Postgres
+const KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "between",
+    "binary",
+    "both",
+    "by",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "delete",
+    "desc",
+    "distinct",
+    "do",
+    "drop",
+    "else",
+    "end",
+    "except",
+    "exists",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "index",
+    "initially",
+    "inner",
+    "insert",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "key",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "set",
+    "similar",
+    "some",
+    "symmetric",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "update",
+    "user",
+    "using",
+    "values",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// The SQL dialect generated by `Schema::to_sql`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// PostgreSQL, with `VARCHAR(n)`, native arrays and `JSONB`
+    Postgres,
+    /// SQLite, with its `INTEGER`, `REAL` and `TEXT` storage classes
+    Sqlite,
+}
+
+impl SqlDialect {
+    fn primary_key(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY",
+            SqlDialect::Sqlite => "INTEGER PRIMARY KEY",
+        }
+    }
+
+    fn integer(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "BIGINT",
+            SqlDialect::Sqlite => "INTEGER",
+        }
+    }
+
+    // Integers above `i64::MAX`, which neither has a 64-bit integer type for
+    fn big_integer(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "NUMERIC(20)",
+            SqlDialect::Sqlite => "NUMERIC",
+        }
+    }
+
+    fn float(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "DOUBLE PRECISION",
+            SqlDialect::Sqlite => "REAL",
+        }
+    }
+
+    fn boolean(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "BOOLEAN",
+            SqlDialect::Sqlite => "INTEGER",
+        }
+    }
+
+    // Postgres doesn't allow `VARCHAR(0)`, for keys that only held empty strings
+    fn string(self, max_len: usize) -> String {
+        match self {
+            SqlDialect::Postgres => format!("VARCHAR({})", max_len.max(1)),
+            SqlDialect::Sqlite => "TEXT".into(),
+        }
+    }
+
+    fn json(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "JSONB",
+            SqlDialect::Sqlite => "TEXT",
+        }
+    }
+}
+
+impl Schema {
+    /// Generates `CREATE TABLE` statements for the schema in the given SQL dialect.
+    ///
+    /// Nested objects are flattened into `parent_child` columns and arrays of objects get a
+    /// child table with a foreign key to their parent. Columns are `NOT NULL` when the key was
+    /// present in every object and never `NULL`. Keys with several types are stored as JSON.
+    pub fn to_sql(&self, dialect: SqlDialect) -> String {
+        let mut table_names = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(TableSource {
+            name: unique_field_name(&mut table_names, snake_case(&self.name)),
+            parent: None,
+            shapes: vec![self],
+        });
+
+        let mut tables = Vec::new();
+        while let Some(source) = queue.pop_front() {
+            let mut columns = Vec::new();
+            let mut children = Vec::new();
+            flatten(&source.shapes, &[], true, &mut columns, &mut children);
+
+            let table = Table::new(&source, columns, dialect);
+            for (path, shapes) in children {
+                let name = format!("{}_{}", source.name, path.join("_"));
+                queue.push_back(TableSource {
+                    name: unique_field_name(&mut table_names, name),
+                    parent: Some((source.name.clone(), table.primary_key.clone())),
+                    shapes,
+                });
+            }
+            tables.push(table.to_sql(dialect));
+        }
+
+        tables.join("\n")
+    }
+}
+
+// The objects a table is created for, and the table and primary key its rows refer to
+struct TableSource<'a> {
+    name: String,
+    parent: Option<(String, String)>,
+    shapes: Vec<&'a Schema>,
+}
+
+struct Column {
+    name: String,
+    v_types: Vec<SchemaValueType>,
+    not_null: bool,
+}
+
+struct Table {
+    name: String,
+    primary_key: String,
+    foreign_key: Option<(String, String, String)>,
+    columns: Vec<(String, String, bool)>,
+}
+
+impl Table {
+    // The columns keep their names, the key columns get a suffix if they clash with one
+    fn new(source: &TableSource, columns: Vec<Column>, dialect: SqlDialect) -> Self {
+        let mut names = HashSet::new();
+        let columns = columns
+            .into_iter()
+            .map(|column| (unique_field_name(&mut names, column.name), sql_type(&column.v_types, dialect), column.not_null))
+            .collect::<Vec<(String, String, bool)>>();
+
+        let primary_key = unique_field_name(&mut names, "id".into());
+        let foreign_key = source
+            .parent
+            .as_ref()
+            .map(|(table, key)| (unique_field_name(&mut names, format!("{}_id", table)), table.clone(), key.clone()));
+
+        Self {
+            name: source.name.clone(),
+            primary_key,
+            foreign_key,
+            columns,
+        }
+    }
+
+    fn to_sql(&self, dialect: SqlDialect) -> String {
+        let mut definitions = vec![format!("{} {}", identifier(&self.primary_key), dialect.primary_key())];
+        if let Some((name, table, key)) = &self.foreign_key {
+            definitions.push(format!("{} {} NOT NULL REFERENCES {} ({})", identifier(name), dialect.integer(), identifier(table), identifier(key)));
+        }
+        for (name, sql_type, not_null) in &self.columns {
+            let constraint = if *not_null { " NOT NULL" } else { "" };
+            definitions.push(format!("{} {}{}", identifier(name), sql_type, constraint));
+        }
+
+        format!("CREATE TABLE {} (\n    {}\n);\n", identifier(&self.name), definitions.join(",\n    "))
+    }
+}
+
+// Adds a column for every key of the shapes, nested objects are added with their key as prefix
+// and arrays of objects are added to `children`. The shapes share columns for the same keys.
+fn flatten<'a>(shapes: &[&'a Schema], prefix: &[String], not_null: bool, columns: &mut Vec<Column>, children: &mut Vec<(Vec<String>, Vec<&'a Schema>)>) {
+    let mut keys: IndexMap<&str, Vec<SchemaValueType>> = IndexMap::new();
+    for shape in shapes {
+        for (key, v_types) in &shape.map {
+            keys.entry(key)
+                .or_default()
+                .extend(v_types.iter().cloned());
+        }
+    }
+
+    for (key, v_types) in keys {
+        let mut path = prefix.to_vec();
+        path.push(snake_case(key));
+
+        let is_required = shapes.iter().all(|shape| shape.is_required(key));
+        let not_null = not_null && is_required && !is_nullable(&v_types);
+
+        // The schemas are taken from the shapes rather than the merged types, so they outlive them
+        if objects(&v_types).is_some() {
+            let nested = shapes
+                .iter()
+                .filter_map(|shape| shape.map.get(key))
+                .flat_map(|v_types| objects(v_types).unwrap_or_default())
+                .collect::<Vec<&Schema>>();
+            flatten(&nested, &path, not_null, columns, children);
+            continue;
+        }
+        if array_objects(&v_types).is_some() {
+            let items = shapes
+                .iter()
+                .filter_map(|shape| shape.map.get(key))
+                .flat_map(|v_types| array_objects(v_types).unwrap_or_default())
+                .collect::<Vec<&Schema>>();
+            children.push((path, items));
+            continue;
+        }
+
+        columns.push(Column {
+            name: path.join("_"),
+            v_types,
+            not_null,
+        });
+    }
+}
+
+// The objects of a key whose types are objects only
+fn objects(v_types: &[SchemaValueType]) -> Option<Vec<&Schema>> {
+    let types = non_null_types(v_types);
+    let objects = types
+        .iter()
+        .filter_map(|v_type| match v_type {
+            SchemaValueType::Object(schema) => Some(schema),
+            _ => None,
+        })
+        .collect::<Vec<&Schema>>();
+
+    match !objects.is_empty() && objects.len() == types.len() {
+        true => Some(objects),
+        false => None,
+    }
+}
+
+// The objects of a key whose types are arrays of objects only
+fn array_objects(v_types: &[SchemaValueType]) -> Option<Vec<&Schema>> {
+    let types = non_null_types(v_types);
+    let mut objects_of_arrays = Vec::new();
+    for v_type in &types {
+        match v_type {
+            SchemaValueType::Array(items) => objects_of_arrays.extend(objects(items)?),
+            _ => return None,
+        }
+    }
+
+    match objects_of_arrays.is_empty() {
+        true => None,
+        false => Some(objects_of_arrays),
+    }
+}
+
+fn sql_type(v_types: &[SchemaValueType], dialect: SqlDialect) -> String {
+    let types = non_null_types(v_types);
+    let max_len = types
+        .iter()
+        .filter_map(|v_type| match v_type {
+            SchemaValueType::String(_, max) => Some(*max),
+            _ => None,
+        })
+        .max();
+
+    let all = |f: fn(&SchemaValueType) -> bool| !types.is_empty() && types.iter().all(|v_type| f(v_type));
+    if all(|v_type| matches!(v_type, SchemaValueType::Primitive(name) if name == "BOOL")) {
+        return dialect.boolean().into();
+    }
+    if let ValueKind::Integer(width) = value_kind(v_types) {
+        return match width {
+            IntegerWidth::I64 => dialect.integer().into(),
+            IntegerWidth::U64 | IntegerWidth::Wider => dialect.big_integer().into(),
+        };
+    }
+    if all(|v_type| matches!(v_type, SchemaValueType::Float(..))) {
+        return dialect.float().into();
+    }
+    if let (true, Some(max_len)) = (all(|v_type| matches!(v_type, SchemaValueType::String(..))), max_len) {
+        return dialect.string(max_len);
+    }
+
+    // Postgres has arrays of scalars, everything else is stored as JSON
+    if dialect == SqlDialect::Postgres && all(|v_type| matches!(v_type, SchemaValueType::Array(_))) {
+        let items = types
+            .iter()
+            .filter_map(|v_type| match v_type {
+                SchemaValueType::Array(items) => Some(items.iter().cloned()),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<SchemaValueType>>();
+
+        let item_type = sql_type(&items, dialect);
+        if item_type != dialect.json() && !item_type.ends_with("[]") {
+            return format!("{}[]", item_type);
+        }
+    }
+
+    dialect.json().into()
+}
+
+fn identifier(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("\"{}\"", name),
+        false => name.into(),
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use super::{camel_case, integer_width, is_nullable, most_specific_first, non_null_types, pascal_case, unique_field_name, IntegerWidth, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+// Standard library and Foundation types that generated types must not shadow
+const RESERVED: &[&str] = &[
+    "Any",
+    "Array",
+    "Bool",
+    "Codable",
+    "CodingKey",
+    "CodingKeys",
+    "Data",
+    "Date",
+    "Decimal",
+    "Decoder",
+    "Dictionary",
+    "Double",
+    "Encoder",
+    "Error",
+    "Int",
+    "JSONValue",
+    "Optional",
+    "Result",
+    "Self",
+    "Set",
+    "String",
+    "Type",
+    "UInt64",
+    "URL",
+];
+
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+// A Codable value of any JSON type, for values whose type isn't known
+const JSON_VALUE: &str = "enum JSONValue: Codable {
+    case null
+    case bool(Bool)
+    case number(Double)
+    case string(String)
+    case array([JSONValue])
+    case object([String: JSONValue])
+
+    init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        if container.decodeNil() {
+            self = .null
+        } else if let value = try? container.decode(Bool.self) {
+            self = .bool(value)
+        } else if let value = try? container.decode(Double.self) {
+            self = .number(value)
+        } else if let value = try? container.decode(String.self) {
+            self = .string(value)
+        } else if let value = try? container.decode([JSONValue].self) {
+            self = .array(value)
+        } else {
+            self = .object(try container.decode([String: JSONValue].self))
+        }
+    }
+
+    func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        switch self {
+        case .null:
+            try container.encodeNil()
+        case .bool(let value):
+            try container.encode(value)
+        case .number(let value):
+            try container.encode(value)
+        case .string(let value):
+            try container.encode(value)
+        case .array(let value):
+            try container.encode(value)
+        case .object(let value):
+            try container.encode(value)
+        }
+    }
+}
+";
+
+impl Schema {
+    /// Generates Swift `Codable` structs for the schema and all schemas nested in it.
+    ///
+    /// Properties are camelCase, with `CodingKeys` for the keys they don't match. Keys that can
+    /// be `NULL` or are not present in every object are optionals, arrays become `[T]` and keys
+    /// with several types become enums with associated values, e.g. `case string(String)`.
+    pub fn to_swift(&self) -> String {
+        swift(TypeNames::new(self, RESERVED))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Swift `Codable` structs for any root, see `Schema::to_swift`.
+    /// Other roots than objects become a type alias, e.g. `typealias Root = [Int]`.
+    pub fn to_swift(&self) -> String {
+        swift(TypeNames::for_root(self, RESERVED))
+    }
+}
+
+fn swift(names: TypeNames) -> String {
+    let alias = names
+        .alias()
+        .map(|(name, v_types)| (name.to_string(), v_types));
+    let schemas = names
+        .schemas()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect::<Vec<(String, &Schema)>>();
+    let mut emitter = SwiftEmitter {
+        names,
+        enums: Vec::new(),
+        uses_json_value: false,
+    };
+
+    let mut types = Vec::new();
+    if let Some((name, v_types)) = alias {
+        let swift_type = emitter.swift_type(v_types, &format!("{}Value", name));
+        types.push(format!("typealias {} = {}\n", name, swift_type));
+        types.append(&mut emitter.enums);
+    }
+    for (name, schema) in schemas {
+        types.push(emitter.structure(&name, schema));
+        types.append(&mut emitter.enums);
+    }
+    if emitter.uses_json_value {
+        types.push(JSON_VALUE.into());
+    }
+
+    format!("import Foundation\n\n{}", types.join("\n"))
+}
+
+struct SwiftEmitter<'a> {
+    names: TypeNames<'a>,
+    // Enums created for the properties of the current struct
+    enums: Vec<String>,
+    uses_json_value: bool,
+}
+
+impl SwiftEmitter<'_> {
+    fn structure(&mut self, name: &str, schema: &Schema) -> String {
+        let mut property_names = HashSet::new();
+        let mut properties = String::new();
+        let mut coding_keys = String::new();
+        let mut is_renamed = false;
+
+        for (key, v_types) in &schema.map {
+            let mut swift_type = self.swift_type(v_types, &format!("{}{}", name, pascal_case(key)));
+            if !schema.is_required(key) && !swift_type.ends_with('?') {
+                swift_type.push('?');
+            }
+
+            let property_name = unique_field_name(&mut property_names, camel_case(key));
+            let identifier = identifier(&property_name);
+            properties.push_str(&format!("    let {}: {}\n", identifier, swift_type));
+            match property_name == *key {
+                true => coding_keys.push_str(&format!("        case {}\n", identifier)),
+                false => {
+                    is_renamed = true;
+                    coding_keys.push_str(&format!("        case {} = {}\n", identifier, serde_json::Value::String(key.clone())));
+                }
+            }
+        }
+
+        match is_renamed {
+            true => format!("struct {}: Codable {{\n{}\n    enum CodingKeys: String, CodingKey {{\n{}    }}\n}}\n", name, properties, coding_keys),
+            false => format!("struct {}: Codable {{\n{}}}\n", name, properties),
+        }
+    }
+
+    // A single type is used as-is, several types become an enum called `enum_name`
+    fn swift_type(&mut self, v_types: &[SchemaValueType], enum_name: &str) -> String {
+        let types = non_null_types(v_types);
+        let swift_type = match types[..] {
+            [] => self.json_value(),
+            [v_type] => self.single_type(v_type, enum_name),
+            _ => self.enumeration(&types, enum_name),
+        };
+
+        match is_nullable(v_types) {
+            true => format!("{}?", swift_type),
+            false => swift_type,
+        }
+    }
+
+    fn single_type(&mut self, v_type: &SchemaValueType, enum_name: &str) -> String {
+        match v_type {
+            SchemaValueType::Primitive(name) if name == "BOOL" => "Bool".into(),
+            SchemaValueType::Primitive(_) => self.json_value(),
+            SchemaValueType::Integer(min, max) => match integer_width(*min, *max) {
+                IntegerWidth::I64 => "Int".into(),
+                IntegerWidth::U64 => "UInt64".into(),
+                IntegerWidth::Wider => "Decimal".into(),
+            },
+            SchemaValueType::Float(..) => "Double".into(),
+            SchemaValueType::String(..) => "String".into(),
+            SchemaValueType::Array(v_types) => format!("[{}]", self.swift_type(v_types, &format!("{}Item", enum_name))),
+            SchemaValueType::Object(schema) => self.names.name_of(schema).into(),
+        }
+    }
+
+    // Decoding tries the cases in order and keeps the first one that matches
+    fn enumeration(&mut self, types: &[&SchemaValueType], enum_name: &str) -> String {
+        let enum_name = self.names.unique(enum_name);
+        let mut cases = String::new();
+        let mut decode = String::new();
+        let mut encode = String::new();
+
+        for v_type in most_specific_first(types) {
+            let swift_type = self.single_type(v_type, &format!("{}Item", enum_name));
+            let case_name = identifier(&case_name(v_type, &swift_type));
+            cases.push_str(&format!("    case {}({})\n", case_name, swift_type));
+            decode.push_str(&format!(
+                "        if let value = try? container.decode({}.self) {{\n            self = .{}(value)\n            return\n        }}\n",
+                swift_type, case_name
+            ));
+            encode.push_str(&format!("        case .{}(let value):\n            try container.encode(value)\n", case_name));
+        }
+
+        self.enums.push(format!(
+            "enum {0}: Codable {{\n{1}\n    init(from decoder: Decoder) throws {{\n        let container = try decoder.singleValueContainer()\n{2}        throw DecodingError.typeMismatch({0}.self, DecodingError.Context(codingPath: decoder.codingPath, debugDescription: \"Unexpected value for {0}\"))\n    }}\n\n    func encode(to encoder: Encoder) throws {{\n        var container = encoder.singleValueContainer()\n        switch self {{\n{3}        }}\n    }}\n}}\n",
+            enum_name, cases, decode, encode
+        ));
+        enum_name
+    }
+
+    fn json_value(&mut self) -> String {
+        self.uses_json_value = true;
+        "JSONValue".into()
+    }
+}
+
+fn case_name(v_type: &SchemaValueType, swift_type: &str) -> String {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => "bool".into(),
+        SchemaValueType::Primitive(_) => "value".into(),
+        SchemaValueType::Integer(..) => "integer".into(),
+        SchemaValueType::Float(..) => "double".into(),
+        SchemaValueType::String(..) => "string".into(),
+        SchemaValueType::Array(_) => "array".into(),
+        SchemaValueType::Object(_) => camel_case(swift_type),
+    }
+}
+
+// Keywords are escaped with backticks
+fn identifier(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("`{}`", name),
+        false => name.into(),
+    }
+}
@@ -0,0 +1,104 @@
+use super::{is_nullable, non_null_types, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+// Global types that generated interfaces must not shadow
+pub(super) const RESERVED: &[&str] = &["Array", "Boolean", "Date", "Error", "Map", "Number", "Object", "Promise", "Record", "Set", "String", "Symbol"];
+
+impl Schema {
+    /// Generates TypeScript interfaces for the schema and all schemas nested in it.
+    ///
+    /// Keys that are not present in every object are optional (`?`), keys with several
+    /// types become unions and arrays become `T[]` or `(A | B)[]`.
+    pub fn to_typescript(&self) -> String {
+        typescript(&TypeNames::new(self, RESERVED))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates TypeScript for any root, see `Schema::to_typescript`.
+    /// Other roots than objects become a type alias, e.g. `export type Root = (Item | number)[];`
+    pub fn to_typescript(&self) -> String {
+        typescript(&TypeNames::for_root(self, RESERVED))
+    }
+}
+
+fn typescript(names: &TypeNames) -> String {
+    let alias = names
+        .alias()
+        .map(|(name, v_types)| format!("export type {} = {};\n", name, union(names, v_types)));
+
+    alias
+        .into_iter()
+        .chain(
+            names
+                .schemas()
+                .map(|(name, schema)| interface(names, name, schema)),
+        )
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn interface(names: &TypeNames, name: &str, schema: &Schema) -> String {
+    let mut interface = format!("export interface {} {{\n", name);
+
+    for (key, v_types) in &schema.map {
+        let optional = if schema.is_required(key) { "" } else { "?" };
+        interface.push_str(&format!("  {}{}: {};\n", property_name(key), optional, union(names, v_types)));
+    }
+
+    interface.push_str("}\n");
+    interface
+}
+
+fn union(names: &TypeNames, v_types: &[SchemaValueType]) -> String {
+    let mut types = Vec::new();
+    for v_type in non_null_types(v_types) {
+        let ts_type = ts_type(names, v_type);
+        if !types.contains(&ts_type) {
+            types.push(ts_type);
+        }
+    }
+
+    if is_nullable(v_types) {
+        types.push("null".into());
+    }
+
+    match types.is_empty() {
+        true => "unknown".into(),
+        false => types.join(" | "),
+    }
+}
+
+fn ts_type(names: &TypeNames, v_type: &SchemaValueType) -> String {
+    match v_type {
+        SchemaValueType::Primitive(name) => match name.as_str() {
+            "NULL" => "null".into(),
+            "BOOL" => "boolean".into(),
+            _ => "unknown".into(),
+        },
+        SchemaValueType::Integer(..) | SchemaValueType::Float(..) => "number".into(),
+        SchemaValueType::String(..) => "string".into(),
+        SchemaValueType::Array(v_types) => {
+            let items = union(names, v_types);
+            match items.contains(' ') {
+                true => format!("({})[]", items),
+                false => format!("{}[]", items),
+            }
+        }
+        SchemaValueType::Object(schema) => names.name_of(schema).into(),
+    }
+}
+
+// Keys that aren't valid identifiers are quoted
+pub(super) fn property_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    match is_identifier {
+        true => key.into(),
+        false => serde_json::Value::String(key.into()).to_string(),
+    }
+}
@@ -0,0 +1,83 @@
+use super::typescript::{property_name, RESERVED};
+use super::{is_nullable, most_specific_first, non_null_types, TypeNames};
+use crate::{Schema, SchemaRoot, SchemaValueType};
+
+impl Schema {
+    /// Generates Zod schemas for the schema and all schemas nested in it, each with the
+    /// TypeScript type it infers.
+    ///
+    /// Nested schemas come before the schemas using them. Strings are validated against the
+    /// lengths that were found, keys that are not present in every object are `.optional()`,
+    /// keys that can be `NULL` are `.nullable()` and keys with several types use `z.union`.
+    pub fn to_zod(&self) -> String {
+        zod(&TypeNames::new(self, RESERVED))
+    }
+}
+
+impl SchemaRoot {
+    /// Generates Zod schemas for any root, see `Schema::to_zod`.
+    /// Other roots than objects become a schema of their values, e.g. `z.array(z.number().int())`.
+    pub fn to_zod(&self) -> String {
+        zod(&TypeNames::for_root(self, RESERVED))
+    }
+}
+
+fn zod(names: &TypeNames) -> String {
+    let mut schemas = names
+        .schemas()
+        .rev()
+        .map(|(name, schema)| object(names, name, schema))
+        .collect::<Vec<String>>();
+    if let Some((name, v_types)) = names.alias() {
+        schemas.push(format!("export const {0} = {1};\n\nexport type {0} = z.infer<typeof {0}>;\n", name, union(names, v_types)));
+    }
+
+    format!("import {{ z }} from \"zod\";\n\n{}", schemas.join("\n"))
+}
+
+fn object(names: &TypeNames, name: &str, schema: &Schema) -> String {
+    let mut zod = format!("export const {} = z.object({{\n", name);
+
+    for (key, v_types) in &schema.map {
+        let optional = if schema.is_required(key) { "" } else { ".optional()" };
+        zod.push_str(&format!("  {}: {}{},\n", property_name(key), union(names, v_types), optional));
+    }
+
+    zod.push_str(&format!("}});\n\nexport type {0} = z.infer<typeof {0}>;\n", name));
+    zod
+}
+
+fn union(names: &TypeNames, v_types: &[SchemaValueType]) -> String {
+    // `z.union` returns the first schema that matches, and `z.object` strips unknown keys
+    let mut types = Vec::new();
+    for v_type in most_specific_first(&non_null_types(v_types)) {
+        let zod_type = zod_type(names, v_type);
+        if !types.contains(&zod_type) {
+            types.push(zod_type);
+        }
+    }
+
+    let zod = match types.len() {
+        0 if is_nullable(v_types) => return "z.null()".into(),
+        0 => "z.unknown()".into(),
+        1 => types.remove(0),
+        _ => format!("z.union([{}])", types.join(", ")),
+    };
+
+    match is_nullable(v_types) {
+        true => format!("{}.nullable()", zod),
+        false => zod,
+    }
+}
+
+fn zod_type(names: &TypeNames, v_type: &SchemaValueType) -> String {
+    match v_type {
+        SchemaValueType::Primitive(name) if name == "BOOL" => "z.boolean()".into(),
+        SchemaValueType::Primitive(_) => "z.unknown()".into(),
+        SchemaValueType::Integer(..) => "z.number().int()".into(),
+        SchemaValueType::Float(..) => "z.number()".into(),
+        SchemaValueType::String(min, max) => format!("z.string().min({}).max({})", min, max),
+        SchemaValueType::Array(v_types) => format!("z.array({})", union(names, v_types)),
+        SchemaValueType::Object(schema) => names.name_of(schema).into(),
+    }
+}
@@ -14,9 +14,13 @@
 //!
 //! ## Modules
 //!
+//! - `emit`: Generates type definitions for other languages from a `Schema`, e.g. `Schema::to_typescript`.
 //! - `schema`: Defines the `Schema` struct and related functionality for schema creation and manipulation.
+//! - `schema_builder`: Infers a `Schema` incrementally from objects that are added one at a time.
+//! - `schema_error`: Defines the `SchemaError` type returned by the fallible `try_*` functions.
 //! - `schema_object`: Represents individual JSON objects within the schema.
 //! - `schema_object_key`: Handles keys in JSON objects, aiding in structuring the schema.
+//! - `schema_root`: Represents the root of any JSON document, including scalars and arrays of non-objects.
 //! - `schema_value_type`: Enumerates possible types for values in the schema (e.g., string, number, object).
 //! - `value_type`: Identifies and categorizes the types of values found in the JSON data.
 //!
@@ -35,7 +39,8 @@
 //! ```
 //!
 //! The `from_json` function is the primary entry point, taking a `serde_json::Value` and a boolean flag
-//! to indicate whether similar objects should be merged.
+//! to indicate whether similar objects should be merged. It panics on input it can't handle, use
+//! `try_from_json` to get a `SchemaError` instead.
 //!
 //! ## Testing
 //!
@@ -57,15 +62,23 @@
 //!
 //! [End of docstring]
 
+mod emit;
 mod schema;
+mod schema_builder;
+mod schema_error;
 mod schema_object;
 mod schema_object_key;
+mod schema_root;
 mod schema_value_type;
 mod value_type;
 
+pub use emit::{PythonStyle, SqlDialect};
 pub use schema::Schema;
+pub use schema_builder::SchemaBuilder;
+pub use schema_error::SchemaError;
 pub use schema_object::SchemaObject;
 pub use schema_object_key::SchemaObjectKey;
+pub use schema_root::SchemaRoot;
 pub use schema_value_type::SchemaValueType;
 pub use value_type::ValueType;
 
@@ -79,6 +92,19 @@ mod tests {
         Schema::from_json(&serde_json::Value::Null, false);
     }
 
+    #[test]
+    fn test_try_from_json_errors() {
+        let err = Schema::try_from_json(&serde_json::Value::Null, false).unwrap_err();
+        assert!(matches!(err, SchemaError::InvalidRoot));
+
+        let err = SchemaObject::try_from_json(&serde_json::json!([])).unwrap_err();
+        assert!(matches!(err, SchemaError::NotAnObject));
+
+        let ndjson = "{\"name\": \"Martin\"}\n\n{\"name\": ";
+        let err = Schema::try_from_ndjson(ndjson.as_bytes(), true).unwrap_err();
+        assert!(matches!(err, SchemaError::JsonLine { line: 3, .. }));
+    }
+
     #[test]
     fn test_schema_from_object() {
         let json = serde_json::json!({
@@ -100,6 +126,640 @@ mod tests {
         insta::assert_json_snapshot!(Schema::from_json(&json, true).to_json());
     }
 
+    #[test]
+    fn test_json_schema_from_object() {
+        let json = serde_json::json!({
+            "name": "John Doe",
+            "title": "",
+            "age": 43,
+            "active": true,
+            "address": {
+                "street": "10 Downing Street",
+                "city": "London"
+            },
+            "spouse": null,
+            "birthplace": "東京",
+            "phones": [
+                "+44 1234567",
+                "+44 2345678",
+                123456,
+                { "mobile": "+44 3456789" }
+            ]
+        });
+
+        insta::assert_json_snapshot!(Schema::from_json(&json, true).to_json_schema());
+    }
+
+    #[test]
+    fn test_schema_builder_matches_from_json() {
+        let json = serde_json::json!([
+            { "id": 1, "info": { "name": "Martin", "age": 30 }, "tags": [{ "id": "a" }, "b"] },
+            { "id": 2, "info": { "name": "Paul" }, "tags": null },
+            { "id": 3, "info": { "name": "Anna", "age": 41 }, "tags": [{ "id": "cd" }] }
+        ]);
+
+        for merge_objects in [true, false] {
+            let mut builder = SchemaBuilder::new("root".into(), merge_objects);
+            for line in json.as_array().unwrap() {
+                builder.add_json(line);
+            }
+
+            assert_eq!(builder.build(), Schema::from_json(&json, merge_objects));
+        }
+    }
+
+    #[test]
+    fn test_key_presence() {
+        let json = serde_json::json!([
+            { "name": "Martin", "spouse": null },
+            { "name": "Paul" },
+            { "name": "Anna", "spouse": "Paul" },
+            { "name": "Jane" }
+        ]);
+
+        let schema = Schema::from_json(&json, true);
+        assert_eq!(schema.count, 4);
+        assert!(schema.is_required("name"));
+        assert!(!schema.is_required("spouse"));
+        assert_eq!(schema.presence("name"), 1.0);
+        assert_eq!(schema.presence("spouse"), 0.5);
+        assert_eq!(schema.presence("missing"), 0.0);
+    }
+
+    #[test]
+    fn test_number_ranges() {
+        let json = serde_json::json!([
+            { "count": 34, "ratio": 3.2, "mixed": 1, "big": 18446744073709551615u64 },
+            { "count": 65, "ratio": -1.5, "mixed": 0.5 },
+            { "count": -3, "ratio": 1.0, "mixed": -7 }
+        ]);
+
+        let schema = Schema::from_json(&json, true);
+        insta::assert_json_snapshot!(schema.to_json());
+
+        // Integers above `i64::MAX` stay integers
+        assert!(schema.to_rust().contains("pub big: Option<u64>,"));
+    }
+
+    #[test]
+    fn test_arrays_without_objects() {
+        let json = serde_json::json!([
+            { "tags": ["a", "bc"], "scores": [1, 2], "flags": [true, null], "notes": [] },
+            { "tags": ["def"], "scores": [1.5], "flags": [], "notes": [] }
+        ]);
+
+        insta::assert_json_snapshot!(Schema::from_json(&json, true).to_json());
+    }
+
+    #[test]
+    fn test_nested_arrays() {
+        let json = serde_json::json!([
+            {
+                "matrix": [["a", "b"], [1]],
+                "coordinates": [[[13.4, 52.5], [2.35, 48.85]]],
+                "rows": [[{ "id": 1 }, { "id": 2, "label": "two" }], [{ "id": 3 }]]
+            },
+            {
+                "matrix": [["cde"], [], [null]],
+                "coordinates": [[[-74, 40.71]]],
+                "rows": [[{ "id": 4, "label": "four" }]]
+            }
+        ]);
+
+        insta::assert_json_snapshot!(Schema::from_json(&json, false).to_json());
+
+        // Arrays converted on their own are merged the same way
+        let v_type = ValueType::from_json(&serde_json::json!([1, 2, 1, [true], ["a"]]));
+        assert_eq!(
+            v_type.to_schema_value_type(true),
+            SchemaValueType::Array(vec![
+                SchemaValueType::Integer(1, 2),
+                SchemaValueType::Array(vec![SchemaValueType::Primitive("BOOL".into()), SchemaValueType::String(1, 1)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_schema_root() {
+        let json = serde_json::json!({ "name": "Martin" });
+        assert_eq!(SchemaRoot::from_json(&json, true), SchemaRoot::Object(Schema::from_json(&json, true)));
+
+        let json = serde_json::json!([{ "name": "Martin" }, { "name": "Paul" }]);
+        assert_eq!(SchemaRoot::from_json(&json, true), SchemaRoot::Object(Schema::from_json(&json, true)));
+
+        let json = serde_json::json!([]);
+        assert_eq!(SchemaRoot::from_json(&json, true), SchemaRoot::Value(vec![SchemaValueType::Array(Vec::new())]));
+
+        let json = serde_json::json!("hello");
+        assert_eq!(SchemaRoot::from_json(&json, true), SchemaRoot::Value(vec![SchemaValueType::String(5, 5)]));
+
+        let json = serde_json::json!([1, 2, "x", [true], { "name": "Martin" }]);
+        insta::assert_json_snapshot!(SchemaRoot::from_json(&json, true).to_json());
+
+        let ndjson = "{\"name\": \"Martin\"}\n42\n";
+        let root = SchemaRoot::try_from_ndjson(ndjson.as_bytes(), true).unwrap();
+        insta::assert_json_snapshot!(root.to_json_schema());
+
+        // Lines are elements of a top-level array, for `Schema` as for `SchemaRoot`
+        let ndjson = "{\"name\": \"Martin\"}\n[{\"name\": 42}]\n";
+        let json = serde_json::json!([{ "name": "Martin" }, [{ "name": 42 }]]);
+        assert_eq!(Schema::try_from_ndjson(ndjson.as_bytes(), true).unwrap(), Schema::from_json(&json, true));
+        assert_eq!(SchemaRoot::try_from_ndjson(ndjson.as_bytes(), true).unwrap(), SchemaRoot::from_json(&json, true));
+    }
+
+    #[test]
+    fn test_key_and_type_order() {
+        let json = serde_json::json!([
+            { "name": "Martin", "id": 1, "tags": [{ "id": 1 }, "a", null] },
+            { "name": null, "zip": "12345", "id": "2", "tags": null }
+        ]);
+
+        let mut schema = Schema::from_json(&json, true);
+        assert_eq!(schema.map.keys().collect::<Vec<_>>(), ["name", "id", "tags", "zip"]);
+
+        schema.sort_keys();
+        assert_eq!(schema.map.keys().collect::<Vec<_>>(), ["id", "name", "tags", "zip"]);
+
+        schema.sort_types();
+        insta::assert_json_snapshot!(schema.to_json());
+    }
+
+    #[test]
+    fn test_object_shapes() {
+        let json = serde_json::json!({
+            "items": [
+                { "a": 1 },
+                { "b": 1 },
+                { "a": 2 },
+                { "ab": 1, "c": 1 },
+                { "a": 3, "bc": 1 },
+                { "b": 2 },
+                { "a": 4 }
+            ]
+        });
+
+        let schema = Schema::from_json(&json, false);
+        let shapes = match &schema.map["items"][..] {
+            [SchemaValueType::Array(v_types)] => v_types
+                .iter()
+                .map(|v_type| match v_type {
+                    SchemaValueType::Object(schema) => (schema.map.keys().cloned().collect::<Vec<_>>(), schema.count),
+                    _ => panic!("Expected an object"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected an array"),
+        };
+
+        assert_eq!(
+            shapes,
+            [
+                (vec!["a".to_string()], 3),
+                (vec!["b".to_string()], 2),
+                (vec!["ab".to_string(), "c".to_string()], 1),
+                (vec!["a".to_string(), "bc".to_string()], 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typescript() {
+        let json = serde_json::json!([
+            { "a-b": 1, "date": {}, "tags": ["x", 1, null], "type": "x" },
+            { "a-b": 2, "date": {}, "tags": [], "type": null, "nick": null }
+        ]);
+        let typescript = Schema::from_json(&json, false).to_typescript();
+
+        // Keys that aren't identifiers are quoted, and `Date` is a global type
+        assert!(typescript.contains("  \"a-b\": number;\n  date: Date2;\n"));
+        assert!(typescript.contains("  tags: (number | string | null)[];\n"));
+        // Keys that can be `NULL` are unions with `null`, missing keys are optional
+        assert!(typescript.contains("  type: string | null;\n  nick?: null;\n"));
+        assert!(typescript.ends_with("export interface Date2 {\n}\n"));
+
+        // Other roots than objects are type aliases, with types for the objects in them
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        assert_eq!(root.to_typescript(), "export type Root = (Item | number | string)[];\n\nexport interface Item {\n  name: string;\n}\n");
+        let root = SchemaRoot::from_json(&json, false);
+        assert_eq!(root.to_typescript(), typescript);
+    }
+
+    #[test]
+    fn test_rust() {
+        let json = serde_json::json!([
+            { "type": "a", "self": 1, "a-b": true, "2x": 1, "value": { "v": [1, 2.5] }, "maybe": null },
+            { "type": null, "self": 2, "a-b": false, "2x": 3, "value": { "v": [] } }
+        ]);
+        let rust = Schema::from_json(&json, false).to_rust();
+
+        // Keywords are raw identifiers, unless they can't be, other renamed fields keep their key
+        assert!(rust.contains("    pub r#type: Option<String>,\n    #[serde(rename = \"self\")]\n    pub self_: i64,\n"));
+        assert!(rust.contains("    #[serde(rename = \"a-b\")]\n    pub a_b: bool,\n    #[serde(rename = \"2x\")]\n    pub _2x: i64,\n"));
+        // `Value` is imported by the generated code, missing keys are optional too
+        assert!(rust.contains("    pub value: Value2,\n    pub maybe: Option<serde_json::Value>,\n"));
+        assert!(rust.contains("pub struct Value2 {\n    pub v: Vec<f64>,\n}"));
+
+        // Other roots than objects are type aliases, with an enum for several types
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        let rust = root.to_rust();
+        assert!(rust.contains("pub type Root = Vec<RootValueItem>;\n"));
+        assert!(rust.contains("pub enum RootValueItem {\n    Item(Item),\n    Integer(i64),\n    String(String),\n}\n"));
+        assert!(rust.ends_with("pub struct Item {\n    pub name: String,\n}\n"));
+    }
+
+    #[test]
+    fn test_python() {
+        let json = serde_json::json!([
+            { "class": "a", "model_config": 1, "2fa": true, "int": 1, "a-b": "xy", "nick": null, "empty": {}, "config_dict": { "a": 1 } },
+            { "class": null, "model_config": 2, "2fa": false, "int": 2, "a-b": "z", "empty": {}, "config_dict": { "a": 2 } }
+        ]);
+        let schema = Schema::from_json(&json, false);
+
+        // Keywords, shadowed names and Pydantic's `model_` attributes get a trailing underscore
+        let pydantic = schema.to_python(PythonStyle::Pydantic);
+        assert!(pydantic.contains("    model_config = ConfigDict(protected_namespaces=())\n\n"));
+        assert!(pydantic.contains("    class_: Optional[constr(min_length=1, max_length=1)] = Field(alias=\"class\")\n    model_config_: int = Field(alias=\"model_config\")\n"));
+        assert!(pydantic.contains("    field_2fa: bool = Field(alias=\"2fa\")\n    int_: int = Field(alias=\"int\")\n"));
+        assert!(pydantic.contains("    nick: None = None\n    empty: Empty\n"));
+        assert!(pydantic.contains("class Empty(BaseModel):\n    pass\n"));
+        // Classes don't shadow the imports
+        assert!(pydantic.contains("class ConfigDict2(BaseModel):\n    a: int\n"));
+        assert!(pydantic.contains("    config_dict: ConfigDict2\n"));
+
+        // Dataclasses have no `model_` attributes and don't validate string lengths
+        let dataclass = schema.to_python(PythonStyle::Dataclass);
+        assert!(dataclass.contains("    class_: Optional[str] = field(metadata={\"alias\": \"class\"})\n    model_config: int\n"));
+        assert!(dataclass.contains("    a_b: str = field(metadata={\"alias\": \"a-b\"})\n"));
+
+        // Other roots than objects are type aliases, after the classes they use
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        assert!(root
+            .to_python(PythonStyle::Dataclass)
+            .ends_with("class Item:\n    name: str\n\n\nRoot = list[Union[Item, int, str]]\n"));
+        let root = SchemaRoot::from_json(&serde_json::json!([1, 2]), false);
+        assert_eq!(root.to_python(PythonStyle::Dataclass), "Root = list[int]\n");
+    }
+
+    #[test]
+    fn test_go() {
+        let json = serde_json::json!([
+            { "2fa": true, "東京": "x", "": 1, "a`b": 1, "nick": null, "age": 1, "mixed": "x", "tags": ["a"] },
+            { "2fa": false, "東京": "y", "": 2, "a`b": 2, "nick": "n", "mixed": 1, "tags": null }
+        ]);
+        let go = Schema::from_json(&json, false).to_go();
+
+        // Fields are exported
+        assert!(go.contains("\tField2fa bool            `json:\"2fa\"`\n\tField東京  string          `json:\"東京\"`\n"));
+        // Keys that json tags can't name are left out
+        assert!(go.contains("type Root struct {\n\t// The key \"\" can't be named by a json tag\n\t// The key \"a`b\" can't be named by a json tag\n"));
+        // Keys that can be `NULL` are pointers, missing keys are `omitempty` too
+        assert!(go.contains("\tNick     *string         `json:\"nick\"`\n\tAge      *int64          `json:\"age,omitempty\"`\n"));
+        // Slices are already nillable, several types are left to the caller to decode
+        assert!(go.contains("\tMixed    json.RawMessage `json:\"mixed\"`\n\tTags     []string        `json:\"tags\"`\n"));
+
+        // Other roots than objects get a type of their own
+        let root = SchemaRoot::from_json(&serde_json::json!([1, 2]), false);
+        assert_eq!(root.to_go(), "package schema\n\ntype Root []int64\n");
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        assert!(root
+            .to_go()
+            .contains("type Root []json.RawMessage\n\ntype Item struct {\n"));
+    }
+
+    #[test]
+    fn test_avro() {
+        let json = serde_json::json!([
+            { "a-b": 1, "2fa": true, "nick": null, "pet": { "kind": "dog" }, "tags": ["x", 1] },
+            { "a-b": 2, "2fa": false, "nick": "n", "tags": [] }
+        ]);
+
+        // Keys become valid names, `NULL` and missing keys are unions with `null`, the latter with a default
+        assert_eq!(
+            Schema::from_json(&json, false).to_avro().unwrap(),
+            serde_json::json!({
+                "type": "record",
+                "name": "Root",
+                "fields": [
+                    { "name": "a_b", "type": "long" },
+                    { "name": "_2fa", "type": "boolean" },
+                    { "name": "nick", "type": ["null", "string"] },
+                    { "name": "pet", "type": ["null", { "type": "record", "name": "Pet", "fields": [{ "name": "kind", "type": "string" }] }], "default": null },
+                    { "name": "tags", "type": { "type": "array", "items": ["long", "string"] } }
+                ]
+            })
+        );
+
+        let json = serde_json::json!({ "name": "Jane Doe", "aliases": [] });
+        let err = Schema::from_json(&json, false)
+            .to_avro()
+            .unwrap_err();
+        assert!(matches!(err, SchemaError::UnsupportedType { format: "Avro", ref path, .. } if path == "root.aliases"));
+
+        // Other roots than objects use the type of their values
+        let root = SchemaRoot::from_json(&serde_json::json!([1, { "a": "x" }]), false);
+        assert_eq!(
+            root.to_avro().unwrap(),
+            serde_json::json!({
+                "type": "array",
+                "items": [{ "type": "record", "name": "Item", "fields": [{ "name": "a", "type": "string" }] }, "long"]
+            })
+        );
+        let err = SchemaRoot::from_json(&serde_json::json!([]), false)
+            .to_avro()
+            .unwrap_err();
+        assert!(matches!(err, SchemaError::UnsupportedType { format: "Avro", ref path, .. } if path == "root"));
+    }
+
+    #[test]
+    fn test_protobuf() {
+        let json = serde_json::json!([
+            { "a-b": 1, "nick": null, "scores": [[1.5, 2], []], "mixed": "x", "empty": {} },
+            { "a-b": 2, "mixed": 1, "scores": [], "empty": {} }
+        ]);
+        let proto = Schema::from_json(&json, false).to_protobuf();
+
+        // Renamed fields keep their key as `json_name`, `NULL` needs the well-known `Value`
+        assert!(proto.contains("import \"google/protobuf/struct.proto\";\n"));
+        assert!(proto.contains("  int64 a_b = 1 [json_name = \"a-b\"];\n  google.protobuf.Value nick = 2;\n"));
+        // Nested arrays are wrapped in messages, as fields can't repeat twice
+        assert!(proto.contains("  repeated RootScoresItemList scores = 3;\n"));
+        assert!(proto.contains("message RootScoresItemList {\n  repeated double values = 1;\n}\n"));
+        assert!(proto.contains("  oneof mixed {\n    int64 mixed_integer = 4;\n    string mixed_string = 5;\n  }\n  Empty empty = 6;\n"));
+        assert!(proto.ends_with("message Empty {\n}\n"));
+    }
+
+    #[test]
+    fn test_sql() {
+        let json = serde_json::json!([
+            { "user": { "first-name": "a", "age": 3 }, "phones": [{ "mobile": "1" }], "mixed": "x", "tags": ["a"] },
+            { "user": { "first-name": "bb" }, "phones": null, "mixed": 1, "tags": [] }
+        ]);
+        let schema = Schema::from_json(&json, false);
+
+        // Objects are flattened, arrays of objects get a child table and several types are JSON
+        assert_eq!(
+            schema.to_sql(SqlDialect::Postgres),
+            "CREATE TABLE root (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    user_first_name VARCHAR(2) NOT NULL,
+    user_age BIGINT,
+    mixed JSONB NOT NULL,
+    tags VARCHAR(1)[] NOT NULL
+);
+
+CREATE TABLE root_phones (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    root_id BIGINT NOT NULL REFERENCES root (id),
+    mobile VARCHAR(1) NOT NULL
+);
+"
+        );
+
+        // SQLite has no arrays and stores JSON as text
+        let sqlite = schema.to_sql(SqlDialect::Sqlite);
+        assert!(sqlite.contains("    id INTEGER PRIMARY KEY,\n    user_first_name TEXT NOT NULL,\n"));
+        assert!(sqlite.contains("    mixed TEXT NOT NULL,\n    tags TEXT NOT NULL\n"));
+        assert!(sqlite.contains("    root_id INTEGER NOT NULL REFERENCES root (id),\n"));
+
+        // Reserved key words are quoted
+        let json = serde_json::json!({ "for": 1, "array": [1], "only": "x", "window": 2, "grant": true, "cast": 1 });
+        let postgres = Schema::from_json(&json, false).to_sql(SqlDialect::Postgres);
+        assert!(postgres.contains("    \"for\" BIGINT NOT NULL,\n    \"array\" BIGINT[] NOT NULL,\n    \"only\" VARCHAR(1) NOT NULL,\n"));
+        assert!(postgres.contains("    \"window\" BIGINT NOT NULL,\n    \"grant\" BOOLEAN NOT NULL,\n    \"cast\" BIGINT NOT NULL\n"));
+    }
+
+    #[test]
+    fn test_graphql() {
+        let json = serde_json::json!([
+            { "a-b": 1, "__x": 1, "countryCode": "a", "country-code": "b", "id": 4_294_967_296_i64, "empty": {}, "pet": { "kind": "dog" } },
+            { "a-b": 2, "__x": 2, "countryCode": "a", "country-code": "b", "id": 1, "pet": { "kind": "cat", "lives": 9 } }
+        ]);
+        let graphql = Schema::from_json(&json, false).to_graphql();
+
+        // Invalid and reserved (`__`) names are camelCased, names that clash get a suffix
+        assert!(graphql.contains("  aB: Int!\n  x: Int!\n  countryCode: String!\n  countryCode_2: String!\n"));
+        // `Int` is 32-bit, objects without fields can't be types and missing keys are nullable
+        assert!(graphql.contains("  id: Float!\n  empty: JSON\n  pet: RootPet!\n"));
+        assert!(graphql.starts_with("scalar JSON\n"));
+        assert!(graphql.contains("union RootPet = Pet | Pet2\n"));
+    }
+
+    #[test]
+    fn test_jtd() {
+        let json = serde_json::json!([
+            { "age": 34, "big": 4_294_967_296_i64, "pet": { "kind": "dog", "good": true }, "phones": ["1", null], "mixed": 1 },
+            { "age": 7, "big": 1, "pet": { "kind": "cat", "lives": 9 }, "phones": null, "mixed": "x", "nick": "n" }
+        ]);
+
+        // Discriminators need the strings, which are only kept when tracked
+        let mut builder = SchemaBuilder::new("root".into(), false).track_strings(true);
+        builder.add_json(&json);
+        let schema = builder.build();
+        assert_eq!(
+            schema.to_jtd(),
+            serde_json::json!({
+                "properties": {
+                    "age": { "type": "int32" },
+                    "big": { "type": "float64" },
+                    "pet": {
+                        "discriminator": "kind",
+                        "mapping": {
+                            "dog": { "properties": { "good": { "type": "boolean" } } },
+                            "cat": { "properties": { "lives": { "type": "int32" } } }
+                        }
+                    },
+                    "phones": { "elements": { "type": "string", "nullable": true }, "nullable": true },
+                    "mixed": {}
+                },
+                "optionalProperties": { "nick": { "type": "string" } }
+            })
+        );
+        assert_eq!(Schema::from_json(&json, false).to_jtd()["properties"]["pet"], serde_json::json!({}));
+        // Tracked strings don't change the schema itself
+        assert_eq!(schema, Schema::from_json(&json, false));
+
+        let json = serde_json::json!([{ "pet": { "kind": "dog", "good": true } }, { "pet": { "kind": "c".repeat(65), "lives": 9 } }]);
+        match SchemaRoot::from_json_with(&json, SchemaBuilder::new("root".into(), false).track_strings(true)) {
+            SchemaRoot::Object(schema) => assert_eq!(schema.to_jtd()["properties"]["pet"], serde_json::json!({})),
+            _ => panic!("Expected an object root"),
+        }
+
+        // Other roots than objects use the form of their values
+        let root = SchemaRoot::from_json(&serde_json::json!([["a"], ["b", null]]), false);
+        assert_eq!(root.to_jtd(), serde_json::json!({ "elements": { "elements": { "type": "string", "nullable": true } } }));
+    }
+
+    #[test]
+    fn test_zod() {
+        let json = serde_json::json!([
+            { "a-b": "xy", "nick": null, "tags": ["a", 1], "empty": {}, "ok": true },
+            { "a-b": "x", "tags": [], "empty": {}, "ok": null }
+        ]);
+        let zod = Schema::from_json(&json, false).to_zod();
+
+        // Nested schemas are declared before they are used
+        assert!(zod.starts_with("import { z } from \"zod\";\n\nexport const Empty = z.object({\n});\n\nexport type Empty = z.infer<typeof Empty>;\n"));
+        assert!(zod.contains("  \"a-b\": z.string().min(1).max(2),\n  nick: z.null().optional(),\n"));
+        assert!(zod.contains("  tags: z.array(z.union([z.number().int(), z.string().min(1).max(1)])),\n"));
+        assert!(zod.contains("  empty: Empty,\n  ok: z.boolean().nullable(),\n"));
+
+        // Other roots than objects are declared last, as they use the other schemas
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        assert!(root
+            .to_zod()
+            .ends_with("export const Root = z.array(z.union([Item, z.number().int(), z.string().min(1).max(1)]));\n\nexport type Root = z.infer<typeof Root>;\n"));
+    }
+
+    #[test]
+    fn test_kotlin() {
+        let json = serde_json::json!([
+            { "in": "a", "a-b": 1, "big": u64::MAX, "nick": null, "empty": {}, "mixed": 1 },
+            { "in": null, "a-b": 2, "big": 1, "empty": {}, "mixed": "x" }
+        ]);
+        let kotlin = Schema::from_json(&json, false).to_kotlin();
+
+        // Keywords are escaped with backticks, renamed properties keep their key with `@SerialName`
+        assert!(kotlin.contains("    val `in`: String?,\n    @SerialName(\"a-b\") val aB: Long,\n    val big: ULong,\n"));
+        // Missing keys default to `null`, several types are left as JSON
+        assert!(kotlin.contains("    val nick: JsonElement? = null,\n    val empty: Empty,\n    val mixed: JsonElement,\n"));
+        // Data classes need at least one property
+        assert!(kotlin.ends_with("@Serializable\nclass Empty\n"));
+
+        // Other roots than objects are type aliases, which need no imports of their own
+        let root = SchemaRoot::from_json(&serde_json::json!([1, 2]), false);
+        assert_eq!(root.to_kotlin(), "typealias Root = List<Long>\n");
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        assert!(root
+            .to_kotlin()
+            .contains("import kotlinx.serialization.json.JsonElement\n\ntypealias Root = List<JsonElement>\n\n@Serializable\ndata class Item(\n"));
+    }
+
+    #[test]
+    fn test_swift() {
+        let json = serde_json::json!([
+            { "self": 1, "a-b": "x", "big": u64::MAX, "nick": null, "empty": {}, "tags": [1, "x"] },
+            { "self": 2, "a-b": "y", "big": 1, "empty": {}, "tags": [] }
+        ]);
+        let swift = Schema::from_json(&json, false).to_swift();
+
+        // Keywords are escaped with backticks, renamed properties keep their key in `CodingKeys`
+        assert!(swift.contains("    let `self`: Int\n    let aB: String\n    let big: UInt64\n"));
+        assert!(swift.contains("        case `self`\n        case aB = \"a-b\"\n"));
+        // Missing keys are optional, several types become an enum
+        assert!(swift.contains("    let nick: JSONValue?\n    let empty: Empty\n    let tags: [RootTagsItem]\n"));
+        assert!(swift.contains("enum RootTagsItem: Codable {\n    case integer(Int)\n    case string(String)\n"));
+        assert!(swift.contains("struct Empty: Codable {\n}\n"));
+        assert!(swift.contains("enum JSONValue: Codable {"));
+
+        // Other roots than objects are type aliases, with an enum for several types
+        let root = SchemaRoot::from_json(&serde_json::json!([1, 2]), false);
+        assert_eq!(root.to_swift(), "import Foundation\n\ntypealias Root = [Int]\n");
+        let root = SchemaRoot::from_json(&serde_json::json!([1, "x", { "name": "a" }]), false);
+        let swift = root.to_swift();
+        assert!(swift.contains("typealias Root = [RootValueItem]\n\nenum RootValueItem: Codable {\n    case item(Item)\n"));
+        assert!(swift.contains("struct Item: Codable {\n    let name: String\n}\n"));
+    }
+
+    #[test]
+    fn test_union_object_order() {
+        // Objects with more keys come first, as the first variant that matches is used
+        let json = serde_json::json!([{ "p": { "a": 1 } }, { "p": { "a": 2, "b": "x" } }, { "p": [1] }]);
+        let schema = Schema::from_json(&json, false);
+
+        assert!(schema
+            .to_rust()
+            .contains("pub enum RootP {\n    P2(P2),\n    P(P),\n    Array(Vec<i64>),\n}"));
+        assert!(schema
+            .to_swift()
+            .contains("enum RootP: Codable {\n    case p2(P2)\n    case p(P)\n    case array([Int])\n"));
+        assert!(schema
+            .to_zod()
+            .contains("p: z.union([P2, P, z.array(z.number().int())]),"));
+    }
+
+    #[test]
+    fn test_java_and_csharp() {
+        let json = serde_json::json!([
+            { "class": "a", "a-b": 1, "2fa": true, "nick": null, "empty": {}, "n": 1 },
+            { "class": null, "a-b": 2, "2fa": false, "empty": {}, "n": "x" }
+        ]);
+        let schema = Schema::from_json(&json, false);
+
+        // Keywords and invalid identifiers are renamed, keeping their key with `@JsonProperty`
+        let java = schema.to_java();
+        assert!(java.contains("    @JsonProperty(\"class\") String class_,\n    @JsonProperty(\"a-b\") long aB,\n    @JsonProperty(\"2fa\") boolean _2fa,\n"));
+        assert!(java.contains("    JsonNode nick,\n    Empty empty,\n    JsonNode n\n"));
+        assert!(java.contains("    public record Empty() {}\n"));
+
+        // Only keys present in every object are `required`, nullable keys are `?`
+        let csharp = schema.to_csharp();
+        assert!(csharp.contains("    [JsonPropertyName(\"class\")]\n    public required string? Class { get; set; }\n"));
+        assert!(csharp.contains("    [JsonPropertyName(\"2fa\")]\n    public required bool _2fa { get; set; }\n"));
+        assert!(csharp.contains("    [JsonPropertyName(\"nick\")]\n    public JsonElement? Nick { get; set; }\n"));
+        assert!(csharp.contains("    public required JsonElement N { get; set; }\n"));
+        assert!(csharp.ends_with("public class Empty\n{\n}\n"));
+    }
+
+    #[test]
+    fn test_arrow() {
+        let json = serde_json::json!([
+            { "id": 1, "big": u64::MAX, "nick": null, "empty": {}, "pet": { "a": 1 }, "n": 1, "tags": ["x"] },
+            { "id": 2, "big": 1, "empty": {}, "pet": { "b": "x" }, "n": "x", "tags": [] }
+        ]);
+        let fields = Schema::from_json(&json, false).to_arrow()["fields"].clone();
+        let json_text = serde_json::json!([
+            { "key": "ARROW:extension:name", "value": "arrow.json" },
+            { "key": "ARROW:extension:metadata", "value": "" }
+        ]);
+
+        assert_eq!(fields[0]["type"], serde_json::json!({ "name": "int", "isSigned": true, "bitWidth": 64 }));
+        assert_eq!(fields[1]["type"], serde_json::json!({ "name": "int", "isSigned": false, "bitWidth": 64 }));
+        // Only `NULL` found
+        assert_eq!(fields[2]["nullable"], true);
+        assert_eq!(fields[2]["type"], serde_json::json!({ "name": "null" }));
+        // Parquet has no empty groups nor unions, both are left as JSON text
+        assert_eq!(fields[3]["name"], "empty");
+        assert_eq!(fields[3]["type"], serde_json::json!({ "name": "utf8" }));
+        assert_eq!(fields[3]["metadata"], json_text);
+        assert_eq!(fields[5]["name"], "n");
+        assert_eq!(fields[5]["metadata"], json_text);
+        // Object shapes are merged, keys missing in some of them are nullable
+        assert_eq!(fields[4]["type"], serde_json::json!({ "name": "struct" }));
+        assert_eq!(fields[4]["children"][0]["nullable"], true);
+        assert_eq!(fields[4]["children"][1]["type"], serde_json::json!({ "name": "utf8" }));
+        assert_eq!(fields[6]["children"][0]["name"], "item");
+    }
+
+    #[test]
+    fn test_bigquery_and_spark() {
+        let json = serde_json::json!([
+            { "c": 1, "C": "x", "a-b": 1, "2fa": true, "nick": null, "empty": {}, "matrix": [[1]], "n": 1 },
+            { "c": 2, "a-b": 2, "2fa": false, "empty": {}, "matrix": [], "n": "x" }
+        ]);
+        let schema = Schema::from_json(&json, false);
+        let column = |name: &str, v_type: &str, mode: &str| serde_json::json!({ "name": name, "type": v_type, "mode": mode });
+
+        // Column names are case-insensitive and can't start with a digit
+        let bigquery = schema.to_bigquery();
+        assert_eq!(bigquery[0], column("c", "INTEGER", "REQUIRED"));
+        assert_eq!(bigquery[1], column("C_2", "STRING", "NULLABLE"));
+        assert_eq!(bigquery[2], column("a_b", "INTEGER", "REQUIRED"));
+        assert_eq!(bigquery[3], column("_2fa", "BOOLEAN", "REQUIRED"));
+        // `NULL`, objects without keys, nested arrays and several types are `JSON`
+        assert_eq!(bigquery[4], column("nick", "JSON", "NULLABLE"));
+        assert_eq!(bigquery[5], column("empty", "JSON", "REQUIRED"));
+        assert_eq!(bigquery[6], column("matrix", "JSON", "REPEATED"));
+        assert_eq!(bigquery[7], column("n", "JSON", "REQUIRED"));
+
+        // Spark keeps the keys as they are
+        let spark = &schema.to_spark()["fields"];
+        assert_eq!(spark[1], serde_json::json!({ "name": "C", "type": "string", "nullable": true, "metadata": {} }));
+        assert_eq!(spark[2]["name"], "a-b");
+        assert_eq!(spark[4], serde_json::json!({ "name": "nick", "type": "string", "nullable": true, "metadata": {} }));
+        assert_eq!(spark[5]["type"], serde_json::json!({ "type": "struct", "fields": [] }));
+        assert_eq!(spark[6]["type"]["elementType"]["elementType"], "long");
+        assert_eq!(spark[7]["type"], "string");
+    }
+
     #[test]
     fn test_schema_from_array_merged() {
         let json = serde_json::json!([
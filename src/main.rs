@@ -1,6 +1,64 @@
-use std::fs;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use std::process;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use schermz::{PythonStyle, Schema, SchemaBuilder, SchemaError, SchemaRoot, SqlDialect};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// The schermz format, e.g. `{"key": {"types": ["STRING(4, 6)"]}}`
+    Schermz,
+    /// JSON Schema (draft 2020-12)
+    JsonSchema,
+    /// TypeScript interfaces
+    Typescript,
+    /// Rust structs deriving serde's Serialize and Deserialize
+    Rust,
+    /// Python Pydantic v2 models
+    Pydantic,
+    /// Python dataclasses
+    Dataclass,
+    /// Go structs with json tags
+    Go,
+    /// GraphQL SDL types
+    Graphql,
+    /// Apache Avro schema (.avsc)
+    Avro,
+    /// Protocol Buffers (proto3) messages
+    Protobuf,
+    /// JSON Type Definition (RFC 8927)
+    Jtd,
+    /// Zod schemas
+    Zod,
+    /// Kotlin data classes for kotlinx.serialization
+    Kotlin,
+    /// Swift Codable structs
+    Swift,
+    /// Java records for Jackson
+    Java,
+    /// C# classes for System.Text.Json
+    Csharp,
+    /// PostgreSQL CREATE TABLE statements
+    Postgres,
+    /// SQLite CREATE TABLE statements
+    Sqlite,
+    /// Apache Arrow schema, for Parquet files
+    Arrow,
+    /// BigQuery table schema
+    Bigquery,
+    /// Spark StructType
+    Spark,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum KeyOrder {
+    /// Sort keys alphabetically
+    Alphabetical,
+    /// Keep keys in the order they were first found in
+    Source,
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -10,13 +68,85 @@ struct Args {
     /// Whether to merge object types into one
     #[arg(short, long)]
     merge_objects: bool,
+    /// The output format
+    #[arg(short, long, value_enum, default_value_t = Format::Schermz)]
+    format: Format,
+    /// The order of the keys
+    #[arg(short, long, value_enum, default_value_t = KeyOrder::Alphabetical)]
+    key_order: KeyOrder,
+    /// Sort the types of every key into a canonical order (NULL, BOOL, INTEGER, FLOAT, STRING, ARRAY, OBJECT)
+    #[arg(short, long)]
+    canonical_types: bool,
+    /// Read the file as newline-delimited JSON (implied by the .ndjson and .jsonl extensions)
+    #[arg(long)]
+    ndjson: bool,
+}
+
+fn is_ndjson_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("ndjson") || ext.eq_ignore_ascii_case("jsonl"))
+        .unwrap_or(false)
+}
+
+fn run(args: &Args) -> Result<(), SchemaError> {
+    // Strings are only kept for the discriminators of JSON Type Definition
+    let builder = SchemaBuilder::new("root".into(), args.merge_objects).track_strings(args.format == Format::Jtd);
+    let mut root = match args.ndjson || is_ndjson_path(&args.file) {
+        // Reads one line at a time, so the file is never held in memory
+        true => SchemaRoot::try_from_ndjson_with(BufReader::new(File::open(&args.file)?), builder)?,
+        false => {
+            let data = fs::read_to_string(&args.file)?;
+            let json: serde_json::Value = serde_json::from_str(&data)?;
+            SchemaRoot::from_json_with(&json, builder)
+        }
+    };
+    if args.key_order == KeyOrder::Alphabetical {
+        root.sort_keys();
+    }
+    if args.canonical_types {
+        root.sort_types();
+    }
+    let output = match args.format {
+        Format::Schermz => serde_json::to_string_pretty(&root.to_json())?,
+        Format::JsonSchema => serde_json::to_string_pretty(&root.to_json_schema())?,
+        Format::Typescript => root.to_typescript(),
+        Format::Rust => root.to_rust(),
+        Format::Pydantic => root.to_python(PythonStyle::Pydantic),
+        Format::Dataclass => root.to_python(PythonStyle::Dataclass),
+        Format::Go => root.to_go(),
+        Format::Graphql => object_root(&root, "GraphQL")?.to_graphql(),
+        Format::Avro => serde_json::to_string_pretty(&root.to_avro()?)?,
+        Format::Protobuf => object_root(&root, "Protocol Buffers")?.to_protobuf(),
+        Format::Jtd => serde_json::to_string_pretty(&root.to_jtd())?,
+        Format::Zod => root.to_zod(),
+        Format::Kotlin => root.to_kotlin(),
+        Format::Swift => root.to_swift(),
+        Format::Java => object_root(&root, "Java")?.to_java(),
+        Format::Csharp => object_root(&root, "C#")?.to_csharp(),
+        Format::Postgres => object_root(&root, "SQL")?.to_sql(SqlDialect::Postgres),
+        Format::Sqlite => object_root(&root, "SQL")?.to_sql(SqlDialect::Sqlite),
+        Format::Arrow => serde_json::to_string_pretty(&object_root(&root, "Arrow")?.to_arrow())?,
+        Format::Bigquery => serde_json::to_string_pretty(&object_root(&root, "BigQuery")?.to_bigquery())?,
+        Format::Spark => serde_json::to_string_pretty(&object_root(&root, "Spark")?.to_spark())?,
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
+// The other formats describe a record, message or table at the root, which only objects map to
+fn object_root<'a>(root: &'a SchemaRoot, format: &'static str) -> Result<&'a Schema, SchemaError> {
+    match root {
+        SchemaRoot::Object(schema) => Ok(schema),
+        SchemaRoot::Value(_) => Err(SchemaError::ObjectRootRequired(format)),
+    }
 }
 
 fn main() {
     let args = Args::parse();
-    let data = fs::read_to_string(args.file).expect("Unable to read file");
-    let json: serde_json::Value = serde_json::from_str(&data).expect("Invalid JSON");
-    let schema = schermz::Schema::from_json(&json, args.merge_objects);
-    let pretty = serde_json::to_string_pretty(&schema.to_json()).unwrap();
-    println!("{}", pretty);
+    if let Err(err) = run(&args) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
 }
@@ -1,167 +1,72 @@
-use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
+use std::io::BufRead;
 
-use itertools::Itertools;
+use indexmap::IndexMap;
 use serde_json::Value as JsonValue;
 
-use crate::{SchemaObject, SchemaValueType, ValueType};
+use crate::{SchemaBuilder, SchemaError, SchemaObject, SchemaValueType};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Schema {
     pub name: String,
-    pub map: HashMap<String, Vec<SchemaValueType>>,
+    /// The types of every key, in the order the keys were first found in
+    pub map: IndexMap<String, Vec<SchemaValueType>>,
+    /// The number of objects the schema was inferred from
+    pub count: usize,
+    /// The number of objects each key was present in
+    pub key_counts: HashMap<String, usize>,
+    /// The distinct strings found for each key, in the order they were found in. Only kept by
+    /// builders with `track_strings`, and left out for keys with too many or too long strings.
+    /// Not compared by `PartialEq`, as it doesn't change the shape of the schema.
+    pub string_values: HashMap<String, Vec<String>>,
 }
 
-type CollectedObjects = HashMap<String, Vec<SchemaObject>>;
-
-impl Schema {
-    // Groups objects by a hash created from their keys
-    fn group_objects_by_keys_fingerprint(objects: Vec<SchemaObject>) -> Vec<Vec<SchemaObject>> {
-        objects
-            .into_iter()
-            .group_by(|obj| {
-                let mut hasher = DefaultHasher::new();
-                let sorted_keys = obj
-                    .keys
-                    .clone()
-                    .into_iter()
-                    .map(|obj_key| obj_key.id)
-                    .sorted()
-                    .collect::<Vec<String>>();
-                let stringified_keys = sorted_keys.join("");
-                stringified_keys.hash(&mut hasher);
-                hasher.finish()
-            })
-            .into_iter()
-            .map(|(_, gr)| gr.collect_vec())
-            .collect()
+impl PartialEq for Schema {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.map == other.map && self.count == other.count && self.key_counts == other.key_counts
     }
+}
 
-    fn create_map(objects: Vec<SchemaObject>, merge_objects: bool) -> HashMap<String, Vec<SchemaValueType>> {
-        let mut map = HashMap::<String, Vec<SchemaValueType>>::new();
-        let mut string_lens = HashMap::<String, Vec<usize>>::new();
-        let mut object_types = CollectedObjects::new();
-        let mut array_object_types = CollectedObjects::new();
-        let mut array_primitive_types_map = HashMap::<String, Vec<SchemaValueType>>::new();
-        let mut array_string_lens_map = HashMap::<String, Vec<usize>>::new();
-
-        for obj in objects {
-            for key in &obj.keys {
-                match &key.v_type {
-                    ValueType::Object(obj) => {
-                        // Collect all objects with the same key id into a vector
-                        // so we can merge them together into a single schema
-                        object_types
-                            .entry(key.id.clone())
-                            .or_default()
-                            .push(obj.clone());
-                    }
-                    ValueType::Array(arr) => {
-                        for value_type in arr {
-                            match value_type {
-                                ValueType::Object(obj) => {
-                                    array_object_types
-                                        .entry(key.id.clone())
-                                        .or_default()
-                                        .push(obj.clone());
-                                }
-                                ValueType::String(len) => {
-                                    array_string_lens_map
-                                        .entry(key.id.clone())
-                                        .or_default()
-                                        .push(*len);
-                                }
-                                primitive_type => {
-                                    let entry = array_primitive_types_map
-                                        .entry(key.id.clone())
-                                        .or_default();
-                                    let vtype = primitive_type.to_schema_value_type(merge_objects);
-                                    if !entry.contains(&vtype) {
-                                        entry.push(vtype);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    ValueType::String(len) => {
-                        string_lens
-                            .entry(key.id.clone())
-                            .or_default()
-                            .push(*len);
-                    }
-                    primitive_type => {
-                        let entry = map.entry(key.id.clone()).or_default();
-                        let vtype = primitive_type.to_schema_value_type(merge_objects);
-                        if !entry.contains(&vtype) {
-                            entry.push(vtype);
-                        }
-                    }
-                }
-            }
-        }
+impl Schema {
+    pub(crate) const JSON_SCHEMA_DIALECT: &'static str = "https://json-schema.org/draft/2020-12/schema";
 
-        for (key, value) in string_lens {
-            let min = value.iter().min().unwrap();
-            let max = value.iter().max().unwrap();
-            map.entry(key)
-                .or_default()
-                .push(SchemaValueType::String(*min, *max));
+    pub fn from_objects(name: String, objects: Vec<SchemaObject>, merge_objects: bool) -> Self {
+        let mut builder = SchemaBuilder::new(name, merge_objects);
+        for object in &objects {
+            builder.add_object(object);
         }
+        builder.build()
+    }
 
-        for (key, value) in object_types {
-            match merge_objects {
-                true => {
-                    let name = key.clone();
-                    map.entry(key)
-                        .or_default()
-                        .push(SchemaValueType::Object(Schema::from_objects(name, value, true)));
-                }
-                false => {
-                    for objects_group in Self::group_objects_by_keys_fingerprint(value) {
-                        map.entry(key.clone())
-                            .or_default()
-                            .push(SchemaValueType::Object(Schema::from_objects(key.clone(), objects_group, false)));
-                    }
-                }
-            }
+    /// Sorts the keys alphabetically, including the keys of nested schemas
+    pub fn sort_keys(&mut self) {
+        self.map.sort_keys();
+        for v_types in self.map.values_mut() {
+            v_types
+                .iter_mut()
+                .for_each(SchemaValueType::sort_keys);
         }
+    }
 
-        for (key, value) in array_object_types {
-            let mut all_array_types = Vec::new();
-
-            match merge_objects {
-                true => {
-                    let schema = Schema::from_objects(key.clone(), value, true);
-                    all_array_types = vec![SchemaValueType::Object(schema)];
-                }
-                false => {
-                    for objects_group in Self::group_objects_by_keys_fingerprint(value) {
-                        all_array_types.push(SchemaValueType::Object(Schema::from_objects(key.clone(), objects_group, false)));
-                    }
-                }
-            }
-
-            if let Some(primitive_types) = array_primitive_types_map.get_mut(&key) {
-                all_array_types.append(primitive_types);
-            }
-            if let Some(string_lens) = array_string_lens_map.get_mut(&key) {
-                let min = string_lens.iter().min().unwrap();
-                let max = string_lens.iter().max().unwrap();
-                all_array_types.push(SchemaValueType::String(*min, *max));
-            }
-            map.entry(key)
-                .or_default()
-                .push(SchemaValueType::Array(all_array_types));
+    /// Sorts the types of every key into their canonical order, see `SchemaValueType::sort_types`
+    pub fn sort_types(&mut self) {
+        for v_types in self.map.values_mut() {
+            SchemaValueType::sort_types(v_types);
         }
+    }
 
-        map
+    /// Whether the key was present in every object the schema was inferred from
+    pub fn is_required(&self, key: &str) -> bool {
+        self.key_counts
+            .get(key)
+            .is_some_and(|count| *count == self.count)
     }
 
-    pub(crate) fn from_objects(name: String, objects: Vec<SchemaObject>, merge_objects: bool) -> Self {
-        Self {
-            name,
-            map: Self::create_map(objects, merge_objects),
+    /// The share of objects the key was present in, from 0 to 1
+    pub fn presence(&self, key: &str) -> f64 {
+        match self.count {
+            0 => 0.0,
+            total => *self.key_counts.get(key).unwrap_or(&0) as f64 / total as f64,
         }
     }
 
@@ -177,29 +82,108 @@ impl Schema {
             }
 
             entry.insert("types".into(), serde_json::Value::Array(types));
+            entry.insert("required".into(), self.is_required(key).into());
+            // Rounded to keep the output readable, e.g. 0.67 instead of 0.6666666666666666
+            let presence = (self.presence(key) * 100.0).round() / 100.0;
+            entry.insert("presence".into(), presence.into());
+            entry.insert(
+                "count".into(),
+                self.key_counts
+                    .get(key)
+                    .copied()
+                    .unwrap_or(0)
+                    .into(),
+            );
             map.insert(key.clone(), serde_json::Value::Object(entry));
         }
 
         serde_json::Value::Object(map)
     }
 
+    pub fn to_json_schema(&self) -> JsonValue {
+        Self::json_schema_document(JsonValue::Object(self.json_schema_object()))
+    }
+
+    // Puts the `$schema` keyword in front of a top-level JSON Schema
+    pub(crate) fn json_schema_document(json_schema: JsonValue) -> JsonValue {
+        let mut document = serde_json::Map::new();
+        document.insert("$schema".into(), Self::JSON_SCHEMA_DIALECT.into());
+        if let JsonValue::Object(json_schema) = json_schema {
+            document.extend(json_schema);
+        }
+
+        JsonValue::Object(document)
+    }
+
+    pub(crate) fn json_schema_object(&self) -> serde_json::Map<String, JsonValue> {
+        let mut properties = serde_json::Map::new();
+
+        for (key, value) in &self.map {
+            properties.insert(key.clone(), SchemaValueType::any_of_json_schema(value));
+        }
+
+        let required = self
+            .map
+            .keys()
+            .filter(|key| self.is_required(key))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let mut json_schema = serde_json::Map::new();
+        json_schema.insert("type".into(), "object".into());
+        json_schema.insert("properties".into(), serde_json::Value::Object(properties));
+        if !required.is_empty() {
+            json_schema.insert("required".into(), required.into());
+        }
+        json_schema
+    }
+
+    /// Infers the schema of a JSON object, or of the objects of a JSON array.
+    /// Other elements of the array are ignored, use `SchemaRoot` to keep them.
     pub fn from_json(json: &JsonValue, merge_objects: bool) -> Self {
+        Self::try_from_json(json, merge_objects).expect("Invalid JSON")
+    }
+
+    pub fn try_from_json(json: &JsonValue, merge_objects: bool) -> Result<Self, SchemaError> {
         match json {
-            JsonValue::Object(_) => Self::from_objects("root".into(), vec![SchemaObject::from_json(json)], merge_objects),
-            JsonValue::Array(_) => {
-                let objects = json
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .filter_map(|el| match el {
-                        JsonValue::Object(_) => Some(SchemaObject::from_json(el)),
-                        _ => None,
-                    })
-                    .collect::<Vec<SchemaObject>>();
-
-                Self::from_objects("root".into(), objects, merge_objects)
+            JsonValue::Object(_) | JsonValue::Array(_) => {
+                let mut builder = SchemaBuilder::new("root".into(), merge_objects);
+                builder.add_json(json);
+                Ok(builder.build())
             }
-            _ => panic!("Invalid JSON"),
+            _ => Err(SchemaError::InvalidRoot),
         }
     }
+
+    /// Infers a schema from newline-delimited JSON, reading one line at a time.
+    /// Every line is treated like an element of a top-level array, empty lines are skipped.
+    /// As with `from_json`, lines that aren't objects are ignored, use `SchemaRoot` to keep them.
+    pub fn try_from_ndjson<R: BufRead>(reader: R, merge_objects: bool) -> Result<Self, SchemaError> {
+        let mut builder = SchemaBuilder::new("root".into(), merge_objects);
+        Self::for_each_ndjson_line(reader, |json| {
+            if json.is_object() {
+                builder.add_json(json);
+            }
+            Ok(())
+        })?;
+        Ok(builder.build())
+    }
+
+    pub(crate) fn for_each_ndjson_line<R, F>(reader: R, mut f: F) -> Result<(), SchemaError>
+    where
+        R: BufRead,
+        F: FnMut(&JsonValue) -> Result<(), SchemaError>,
+    {
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let json: JsonValue = serde_json::from_str(&line).map_err(|source| SchemaError::JsonLine { line: index + 1, source })?;
+            f(&json)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use serde_json::Value as JsonValue;
+
+use crate::{Schema, SchemaObject, SchemaRoot, SchemaValueType, ValueType};
+
+/// Infers a `Schema` from objects that are fed in one at a time.
+///
+/// Only the types seen so far are kept, not the objects themselves, so inputs
+/// of any size (e.g. NDJSON files) can be processed without loading them into memory.
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    name: String,
+    pub(crate) merge_objects: bool,
+    track_strings: bool,
+    count: usize,
+    keys: IndexMap<String, KeyTypes>,
+    // The types of the non-object elements added with `add_element`
+    elements: Option<ValueTypes>,
+}
+
+// The types observed for a single key
+#[derive(Debug, Clone, Default)]
+struct KeyTypes {
+    count: usize,
+    types: ValueTypes,
+    strings: StringValues,
+}
+
+// The distinct strings observed for a single key, until there are too many or too long ones to keep
+#[derive(Debug, Clone)]
+enum StringValues {
+    Distinct(Vec<String>),
+    Dropped,
+}
+
+// The types observed for the values of a single key, or for the elements of its arrays.
+// Nested arrays are handled recursively, so every level keeps its own merged element types.
+#[derive(Debug, Clone, Default)]
+struct ValueTypes {
+    primitives: Vec<SchemaValueType>,
+    integer_range: Option<(i128, i128)>,
+    float_range: Option<(f64, f64)>,
+    string_lens: Option<(usize, usize)>,
+    objects: ObjectGroups,
+    array: Option<Box<ValueTypes>>,
+}
+
+// Objects found for a single key, grouped by their shape, i.e. their sorted keys.
+// When objects are merged, all of them end up in a single group.
+#[derive(Debug, Clone, Default)]
+struct ObjectGroups {
+    groups: IndexMap<Vec<String>, SchemaBuilder>,
+}
+
+impl SchemaBuilder {
+    // The name of the objects in a root that isn't made of objects only, like the items of any array
+    const ITEM_NAME: &'static str = "item";
+    /// The number of distinct strings kept for a key, see `track_strings`
+    pub const MAX_STRING_VALUES: usize = 16;
+    /// The length in characters of the longest string kept for a key, see `track_strings`
+    pub const MAX_STRING_VALUE_LEN: usize = 64;
+
+    pub fn new(name: String, merge_objects: bool) -> Self {
+        Self {
+            name,
+            merge_objects,
+            track_strings: false,
+            count: 0,
+            keys: IndexMap::new(),
+            elements: None,
+        }
+    }
+
+    /// Keeps the distinct strings found for every key, which `Schema::to_jtd` needs to find
+    /// discriminators. Keys with more than `MAX_STRING_VALUES` strings, or strings longer than
+    /// `MAX_STRING_VALUE_LEN`, are left out. Off by default, as every string has to be copied.
+    pub fn track_strings(mut self, track_strings: bool) -> Self {
+        self.track_strings = track_strings;
+        self
+    }
+
+    pub fn add_object(&mut self, object: &SchemaObject) {
+        self.count += 1;
+        for key in &object.keys {
+            let key_types = self.keys.entry(key.id.clone()).or_default();
+            key_types.count += 1;
+            key_types
+                .types
+                .add(&key.id, &key.v_type, self.merge_objects, self.track_strings);
+            if let (true, ValueType::String(_)) = (self.track_strings, &key.v_type) {
+                key_types.strings.add(object.strings.get(&key.id));
+            }
+        }
+    }
+
+    /// Adds a JSON object, or every object of a JSON array. Other values are ignored.
+    pub fn add_json(&mut self, json: &JsonValue) {
+        match json {
+            JsonValue::Object(map) => self.add_object(&SchemaObject::from_map(map, self.track_strings)),
+            JsonValue::Array(arr) => {
+                for map in arr.iter().filter_map(JsonValue::as_object) {
+                    self.add_object(&SchemaObject::from_map(map, self.track_strings));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds an element of a top-level array. Objects are added to the schema,
+    /// the types of other values are kept as element types of the root array.
+    pub fn add_element(&mut self, json: &JsonValue) {
+        match json {
+            JsonValue::Object(map) => self.add_object(&SchemaObject::from_map(map, self.track_strings)),
+            _ => self
+                .elements
+                .get_or_insert_with(Default::default)
+                .add(Self::ITEM_NAME, &ValueType::parse(json, self.track_strings), self.merge_objects, self.track_strings),
+        }
+    }
+
+    /// Builds the root of a top-level array made of the elements added so far.
+    /// As long as only objects were added, the root is the schema of those objects,
+    /// an array without any elements is an empty `ARRAY`.
+    pub fn build_root(mut self) -> SchemaRoot {
+        match self.elements.take() {
+            None if self.count == 0 => SchemaRoot::Value(vec![SchemaValueType::Array(Vec::new())]),
+            None => SchemaRoot::Object(self.build()),
+            Some(elements) => {
+                let mut types = elements.build(true);
+                if self.count > 0 {
+                    self.name = Self::ITEM_NAME.into();
+                    types.insert(0, SchemaValueType::Object(self.build()));
+                }
+                SchemaRoot::Value(vec![SchemaValueType::Array(types)])
+            }
+        }
+    }
+
+    pub fn build(self) -> Schema {
+        let mut map = IndexMap::new();
+        let mut key_counts = HashMap::new();
+        let mut string_values = HashMap::new();
+
+        for (key, key_types) in self.keys {
+            key_counts.insert(key.clone(), key_types.count);
+            if let StringValues::Distinct(strings) = key_types.strings {
+                if !strings.is_empty() {
+                    string_values.insert(key.clone(), strings);
+                }
+            }
+            map.insert(key, key_types.types.build(false));
+        }
+
+        Schema {
+            name: self.name,
+            map,
+            count: self.count,
+            key_counts,
+            string_values,
+        }
+    }
+}
+
+impl ValueTypes {
+    fn add(&mut self, key: &str, v_type: &ValueType, merge_objects: bool, track_strings: bool) {
+        match v_type {
+            ValueType::Object(obj) => self
+                .objects
+                .add(key, obj, merge_objects, track_strings),
+            ValueType::Array(arr) => {
+                let array = self.array.get_or_insert_with(Default::default);
+                for value_type in arr {
+                    array.add(key, value_type, merge_objects, track_strings);
+                }
+            }
+            ValueType::Integer(int) => add_to_range(&mut self.integer_range, *int),
+            ValueType::Float(float) => add_to_range(&mut self.float_range, *float),
+            ValueType::String(len) => add_to_range(&mut self.string_lens, *len),
+            primitive_type => add_primitive(&mut self.primitives, primitive_type.to_schema_value_type(merge_objects)),
+        }
+    }
+
+    // Objects are listed last for keys, but first for array elements
+    fn build(self, objects_first: bool) -> Vec<SchemaValueType> {
+        let mut scalars = self.primitives;
+
+        if let Some((min, max)) = self.integer_range {
+            scalars.push(SchemaValueType::Integer(min, max));
+        }
+
+        if let Some((min, max)) = self.float_range {
+            scalars.push(SchemaValueType::Float(min, max));
+        }
+
+        if let Some((min, max)) = self.string_lens {
+            scalars.push(SchemaValueType::String(min, max));
+        }
+
+        let objects = self.objects.build();
+        let mut types = match objects_first {
+            true => objects.chain(scalars).collect_vec(),
+            false => scalars.into_iter().chain(objects).collect_vec(),
+        };
+
+        if let Some(array) = self.array {
+            types.push(SchemaValueType::Array(array.build(true)));
+        }
+
+        types
+    }
+}
+
+impl Default for StringValues {
+    fn default() -> Self {
+        StringValues::Distinct(Vec::new())
+    }
+}
+
+impl StringValues {
+    // Objects that weren't parsed with `track_strings` have no strings, so their keys can't be tracked
+    fn add(&mut self, string: Option<&String>) {
+        if let StringValues::Distinct(strings) = self {
+            match string {
+                Some(string) if strings.contains(string) => {}
+                Some(string) if strings.len() < SchemaBuilder::MAX_STRING_VALUES && string.chars().count() <= SchemaBuilder::MAX_STRING_VALUE_LEN => strings.push(string.clone()),
+                _ => *self = StringValues::Dropped,
+            }
+        }
+    }
+}
+
+impl ObjectGroups {
+    // All objects with the same keys end up in the same group, wherever they are found
+    fn add(&mut self, name: &str, object: &SchemaObject, merge_objects: bool, track_strings: bool) {
+        let shape = match merge_objects {
+            true => Vec::new(),
+            false => Self::shape(object),
+        };
+
+        self.groups
+            .entry(shape)
+            .or_insert_with(|| SchemaBuilder::new(name.into(), merge_objects).track_strings(track_strings))
+            .add_object(object);
+    }
+
+    fn build(self) -> impl Iterator<Item = SchemaValueType> {
+        self.groups
+            .into_values()
+            .map(|builder| SchemaValueType::Object(builder.build()))
+    }
+
+    fn shape(object: &SchemaObject) -> Vec<String> {
+        object
+            .keys
+            .iter()
+            .map(|obj_key| obj_key.id.clone())
+            .sorted()
+            .collect()
+    }
+}
+
+// The types of the elements of an array, merged like the elements of the arrays of a key
+pub(crate) fn merged_value_types(arr: &[ValueType], merge_objects: bool) -> Vec<SchemaValueType> {
+    let mut types = ValueTypes::default();
+    for v_type in arr {
+        types.add("object", v_type, merge_objects, false);
+    }
+    types.build(true)
+}
+
+fn add_to_range<T: PartialOrd + Copy>(range: &mut Option<(T, T)>, value: T) {
+    match range {
+        Some((min, max)) => {
+            if value < *min {
+                *min = value;
+            }
+            if value > *max {
+                *max = value;
+            }
+        }
+        None => *range = Some((value, value)),
+    }
+}
+
+fn add_primitive(primitives: &mut Vec<SchemaValueType>, vtype: SchemaValueType) {
+    if !primitives.contains(&vtype) {
+        primitives.push(vtype);
+    }
+}
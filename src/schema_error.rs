@@ -0,0 +1,55 @@
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The root of the JSON document is neither an object nor an array
+    InvalidRoot,
+    /// A JSON value that was expected to be an object is something else
+    NotAnObject,
+    /// The output format can only describe documents with an object, or an array of objects, at the root
+    ObjectRootRequired(&'static str),
+    /// The output format has no way to describe the types inferred for a key
+    UnsupportedType { format: &'static str, path: String, reason: &'static str },
+    /// The input could not be read
+    Io(io::Error),
+    /// The input is not valid JSON
+    Json(serde_json::Error),
+    /// A line of a newline-delimited JSON input is not valid JSON
+    JsonLine { line: usize, source: serde_json::Error },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::InvalidRoot => write!(f, "expected a JSON object or array at the root"),
+            SchemaError::NotAnObject => write!(f, "expected a JSON object"),
+            SchemaError::ObjectRootRequired(format) => write!(f, "{} output requires an object, or an array of objects, at the root", format),
+            SchemaError::UnsupportedType { format, path, reason } => write!(f, "{} can't represent `{}`: {}", format, path, reason),
+            SchemaError::Io(err) => write!(f, "unable to read input: {}", err),
+            SchemaError::Json(err) => write!(f, "invalid JSON: {}", err),
+            SchemaError::JsonLine { line, source } => write!(f, "invalid JSON on line {}: {}", line, source),
+        }
+    }
+}
+
+impl error::Error for SchemaError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SchemaError::Io(err) => Some(err),
+            SchemaError::Json(err) | SchemaError::JsonLine { source: err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SchemaError {
+    fn from(err: io::Error) -> Self {
+        SchemaError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(err: serde_json::Error) -> Self {
+        SchemaError::Json(err)
+    }
+}
@@ -1,22 +1,39 @@
-use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 
-use crate::{SchemaObjectKey, ValueType};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{SchemaError, SchemaObjectKey, ValueType};
 
 #[derive(Debug, Clone)]
 pub struct SchemaObject {
     pub(crate) keys: Vec<SchemaObjectKey>,
+    // The values of the keys that are strings, only kept for `SchemaBuilder::track_strings`
+    pub(crate) strings: HashMap<String, String>,
 }
 
 impl SchemaObject {
     pub fn from_json(json: &JsonValue) -> Self {
+        Self::try_from_json(json).expect("Invalid JSON object")
+    }
+
+    pub fn try_from_json(json: &JsonValue) -> Result<Self, SchemaError> {
+        Ok(Self::from_map(json.as_object().ok_or(SchemaError::NotAnObject)?, false))
+    }
+
+    // With `track_strings`, the string values of this object and the objects nested in it are kept
+    pub(crate) fn from_map(map: &Map<String, JsonValue>, track_strings: bool) -> Self {
         let mut keys = Vec::new();
+        let mut strings = HashMap::new();
 
-        for (key, value) in json.as_object().unwrap() {
+        for (key, value) in map {
             keys.push(SchemaObjectKey {
                 id: key.clone(),
-                v_type: ValueType::from_json(value),
+                v_type: ValueType::parse(value, track_strings),
             });
+            if let (true, JsonValue::String(string)) = (track_strings, value) {
+                strings.insert(key.clone(), string.clone());
+            }
         }
-        Self { keys }
+        Self { keys, strings }
     }
 }
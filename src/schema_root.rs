@@ -0,0 +1,91 @@
+use std::io::BufRead;
+
+use serde_json::Value as JsonValue;
+
+use crate::{Schema, SchemaBuilder, SchemaError, SchemaValueType, ValueType};
+
+/// The schema of a whole JSON document, whatever its root is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaRoot {
+    /// An object, or an array that only contains objects
+    Object(Schema),
+    /// Any other document, e.g. a scalar or an array of primitives
+    Value(Vec<SchemaValueType>),
+}
+
+impl SchemaRoot {
+    pub fn from_json(json: &JsonValue, merge_objects: bool) -> Self {
+        Self::from_json_with(json, SchemaBuilder::new("root".into(), merge_objects))
+    }
+
+    /// Like `from_json`, with the objects added to `builder`, e.g. one that tracks strings
+    pub fn from_json_with(json: &JsonValue, mut builder: SchemaBuilder) -> Self {
+        match json {
+            JsonValue::Object(_) => {
+                builder.add_element(json);
+                builder.build_root()
+            }
+            JsonValue::Array(arr) => {
+                for el in arr {
+                    builder.add_element(el);
+                }
+                builder.build_root()
+            }
+            _ => SchemaRoot::Value(vec![ValueType::from_json(json).to_schema_value_type(builder.merge_objects)]),
+        }
+    }
+
+    /// Infers the root from newline-delimited JSON, reading one line at a time.
+    /// Every line is treated like an element of a top-level array, empty lines are skipped.
+    pub fn try_from_ndjson<R: BufRead>(reader: R, merge_objects: bool) -> Result<Self, SchemaError> {
+        Self::try_from_ndjson_with(reader, SchemaBuilder::new("root".into(), merge_objects))
+    }
+
+    /// Like `try_from_ndjson`, with the lines added to `builder`, e.g. one that tracks strings
+    pub fn try_from_ndjson_with<R: BufRead>(reader: R, mut builder: SchemaBuilder) -> Result<Self, SchemaError> {
+        Schema::for_each_ndjson_line(reader, |json| {
+            builder.add_element(json);
+            Ok(())
+        })?;
+        Ok(builder.build_root())
+    }
+
+    /// Sorts the keys alphabetically, see `Schema::sort_keys`
+    pub fn sort_keys(&mut self) {
+        match self {
+            SchemaRoot::Object(schema) => schema.sort_keys(),
+            SchemaRoot::Value(v_types) => v_types
+                .iter_mut()
+                .for_each(SchemaValueType::sort_keys),
+        }
+    }
+
+    /// Sorts the types into their canonical order, see `SchemaValueType::sort_types`
+    pub fn sort_types(&mut self) {
+        match self {
+            SchemaRoot::Object(schema) => schema.sort_types(),
+            SchemaRoot::Value(v_types) => SchemaValueType::sort_types(v_types),
+        }
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            SchemaRoot::Object(schema) => schema.to_json(),
+            SchemaRoot::Value(v_types) => {
+                let types = v_types
+                    .iter()
+                    .map(|v| v.to_json())
+                    .collect::<Vec<JsonValue>>();
+
+                serde_json::json!({ "types": types })
+            }
+        }
+    }
+
+    pub fn to_json_schema(&self) -> JsonValue {
+        match self {
+            SchemaRoot::Object(schema) => schema.to_json_schema(),
+            SchemaRoot::Value(v_types) => Schema::json_schema_document(SchemaValueType::any_of_json_schema(v_types)),
+        }
+    }
+}
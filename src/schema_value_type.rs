@@ -5,6 +5,8 @@ use crate::Schema;
 #[derive(Debug, Clone, PartialEq)]
 pub enum SchemaValueType {
     Primitive(String),
+    Integer(i128, i128),
+    Float(f64, f64),
     String(usize, usize),
     Array(Vec<SchemaValueType>),
     Object(Schema),
@@ -14,6 +16,21 @@ impl SchemaValueType {
     pub fn to_json(&self) -> JsonValue {
         match self {
             SchemaValueType::Primitive(name) => JsonValue::String(name.clone()),
+            SchemaValueType::Integer(min, max) => {
+                if min == max {
+                    return JsonValue::String(format!("INTEGER({})", min));
+                }
+
+                JsonValue::String(format!("INTEGER({}, {})", min, max))
+            }
+            // Floats are debug formatted so that whole numbers keep their decimal point, e.g. `FLOAT(1.0, 2.5)`
+            SchemaValueType::Float(min, max) => {
+                if min == max {
+                    return JsonValue::String(format!("FLOAT({:?})", min));
+                }
+
+                JsonValue::String(format!("FLOAT({:?}, {:?})", min, max))
+            }
             SchemaValueType::String(min, max) => {
                 if min == max {
                     return JsonValue::String(format!("STRING({})", min));
@@ -32,4 +49,90 @@ impl SchemaValueType {
             SchemaValueType::Object(schema) => schema.to_json(),
         }
     }
+
+    /// Sorts types into the canonical order `NULL`, `BOOL`, `INTEGER`, `FLOAT`, `STRING`, `ARRAY`, `OBJECT`,
+    /// including the types of nested arrays and schemas. Objects keep the order they were found in.
+    pub fn sort_types(v_types: &mut [SchemaValueType]) {
+        v_types.sort_by_key(SchemaValueType::canonical_rank);
+        for v_type in v_types {
+            match v_type {
+                SchemaValueType::Array(v_types) => Self::sort_types(v_types),
+                SchemaValueType::Object(schema) => schema.sort_types(),
+                _ => {}
+            }
+        }
+    }
+
+    fn canonical_rank(&self) -> u8 {
+        match self {
+            SchemaValueType::Primitive(name) if name == "NULL" => 0,
+            SchemaValueType::Primitive(name) if name == "BOOL" => 1,
+            SchemaValueType::Primitive(_) => 2,
+            SchemaValueType::Integer(..) => 3,
+            SchemaValueType::Float(..) => 4,
+            SchemaValueType::String(..) => 5,
+            SchemaValueType::Array(_) => 6,
+            SchemaValueType::Object(_) => 7,
+        }
+    }
+
+    pub(crate) fn sort_keys(&mut self) {
+        match self {
+            SchemaValueType::Array(v_types) => v_types
+                .iter_mut()
+                .for_each(SchemaValueType::sort_keys),
+            SchemaValueType::Object(schema) => schema.sort_keys(),
+            _ => {}
+        }
+    }
+
+    pub fn to_json_schema(&self) -> JsonValue {
+        match self {
+            SchemaValueType::Primitive(name) => match name.as_str() {
+                "NULL" => serde_json::json!({ "type": "null" }),
+                "BOOL" => serde_json::json!({ "type": "boolean" }),
+                _ => serde_json::json!({}),
+            },
+            SchemaValueType::Integer(min, max) => serde_json::json!({
+                "type": "integer",
+                "minimum": min,
+                "maximum": max,
+            }),
+            SchemaValueType::Float(min, max) => serde_json::json!({
+                "type": "number",
+                "minimum": min,
+                "maximum": max,
+            }),
+            SchemaValueType::String(min, max) => serde_json::json!({
+                "type": "string",
+                "minLength": min,
+                "maxLength": max,
+            }),
+            SchemaValueType::Array(v_types) => {
+                let mut entry = serde_json::Map::new();
+                entry.insert("type".into(), "array".into());
+                if !v_types.is_empty() {
+                    entry.insert("items".into(), Self::any_of_json_schema(v_types));
+                }
+
+                JsonValue::Object(entry)
+            }
+            SchemaValueType::Object(schema) => JsonValue::Object(schema.json_schema_object()),
+        }
+    }
+
+    // A single type is emitted as-is, multiple types are wrapped in `anyOf`
+    pub(crate) fn any_of_json_schema(v_types: &[SchemaValueType]) -> JsonValue {
+        match v_types {
+            [v_type] => v_type.to_json_schema(),
+            _ => {
+                let types = v_types
+                    .iter()
+                    .map(|v| v.to_json_schema())
+                    .collect::<Vec<JsonValue>>();
+
+                serde_json::json!({ "anyOf": types })
+            }
+        }
+    }
 }
@@ -1,12 +1,14 @@
 use serde_json::Value as JsonValue;
 
-use crate::{Schema, SchemaObject, SchemaValueType};
+use crate::schema_builder::merged_value_types;
+use crate::{SchemaBuilder, SchemaObject, SchemaValueType};
 
 #[derive(Debug, Clone)]
 pub enum ValueType {
     Null,
     Bool,
-    Number,
+    Integer(i128),
+    Float(f64),
     String(usize),
     Object(SchemaObject),
     Array(Vec<ValueType>),
@@ -14,17 +16,28 @@ pub enum ValueType {
 
 impl ValueType {
     pub fn from_json(json: &JsonValue) -> Self {
+        Self::parse(json, false)
+    }
+
+    // With `track_strings`, the string values of nested objects are kept, see `SchemaObject::from_map`
+    pub(crate) fn parse(json: &JsonValue, track_strings: bool) -> Self {
         match json {
             JsonValue::Null => Self::Null,
             JsonValue::Bool(_) => Self::Bool,
-            JsonValue::Number(_) => Self::Number,
-            JsonValue::String(_) => {
-                let str = json.as_str().unwrap();
-                Self::String(str.len())
-            }
-            JsonValue::Object(_) => Self::Object(SchemaObject::from_json(json)),
+            // Integers above `i64::MAX` only fit into a u64, both fit into an i128
+            JsonValue::Number(num) => match (num.as_i64(), num.as_u64()) {
+                (Some(int), _) => Self::Integer(int.into()),
+                (None, Some(int)) => Self::Integer(int.into()),
+                (None, None) => Self::Float(num.as_f64().unwrap_or_default()),
+            },
+            // Lengths are counted in characters, like `minLength` and `maxLength` of JSON Schema do
+            JsonValue::String(str) => Self::String(str.chars().count()),
+            JsonValue::Object(map) => Self::Object(SchemaObject::from_map(map, track_strings)),
             JsonValue::Array(arr) => {
-                let values = arr.iter().map(Self::from_json).collect();
+                let values = arr
+                    .iter()
+                    .map(|value| Self::parse(value, track_strings))
+                    .collect();
                 Self::Array(values)
             }
         }
@@ -34,19 +47,15 @@ impl ValueType {
         match self {
             ValueType::Null => SchemaValueType::Primitive("NULL".into()),
             ValueType::Bool => SchemaValueType::Primitive("BOOL".into()),
-            ValueType::Number => SchemaValueType::Primitive("NUMBER".into()),
-            ValueType::Object(obj) => SchemaValueType::Object(Schema::from_objects("object".into(), vec![obj.clone()], merge_objects)),
-            ValueType::Array(arr) => {
-                let mut value_types = arr
-                    .iter()
-                    .map(|value_type| value_type.to_schema_value_type(merge_objects))
-                    .collect::<Vec<SchemaValueType>>();
-
-                value_types.dedup();
-
-                SchemaValueType::Array(value_types)
+            ValueType::Integer(int) => SchemaValueType::Integer(*int, *int),
+            ValueType::Float(float) => SchemaValueType::Float(*float, *float),
+            ValueType::String(len) => SchemaValueType::String(*len, *len),
+            ValueType::Object(obj) => {
+                let mut builder = SchemaBuilder::new("object".into(), merge_objects);
+                builder.add_object(obj);
+                SchemaValueType::Object(builder.build())
             }
-            _ => panic!("Invalid value type"),
+            ValueType::Array(arr) => SchemaValueType::Array(merged_value_types(arr, merge_objects)),
         }
     }
 }